@@ -0,0 +1,487 @@
+use crate::apis::{self, JavaStyle, PythonEnums, PythonStyle, TargetSettings};
+use crate::naming::Naming;
+use crate::sample;
+use crate::template::Template;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use yaml_rust::Yaml;
+
+pub const TARGETS: [&str; 10] = [
+  "scala",
+  "typescript",
+  "rust",
+  "python",
+  "go",
+  "java",
+  "elm",
+  "examples",
+  "arbitrary",
+  "routes",
+];
+
+pub const TEMPLATE_FILE_TYPES: [&str; 3] = ["command", "viewmodel", "response"];
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Config {
+  pub input: Option<String>,
+  pub output: String,
+  pub targets: Vec<String>,
+  pub scala: TargetSettings,
+  pub typescript: TargetSettings,
+  pub rust: TargetSettings,
+  pub python: TargetSettings,
+  pub go: TargetSettings,
+  pub java: TargetSettings,
+  pub elm: TargetSettings,
+  pub python_style: PythonStyle,
+  pub python_enums: PythonEnums,
+  pub java_style: JavaStyle,
+  pub examples: sample::Options,
+}
+
+impl Default for Config {
+  fn default() -> Self {
+    Config {
+      input: None,
+      output: "dist".to_string(),
+      targets: TARGETS.iter().map(|target| target.to_string()).collect(),
+      scala: TargetSettings::default(),
+      typescript: TargetSettings::default(),
+      rust: TargetSettings::default(),
+      python: TargetSettings::default(),
+      go: TargetSettings::default(),
+      java: TargetSettings::default(),
+      elm: TargetSettings::default(),
+      python_style: PythonStyle::Pydantic,
+      python_enums: PythonEnums::Literal,
+      java_style: JavaStyle::Record,
+      examples: sample::Options::default(),
+    }
+  }
+}
+
+fn string(yaml: &Yaml, key: &str) -> Result<Option<String>, String> {
+  match yaml {
+    Yaml::BadValue => Ok(None),
+    Yaml::String(value) => Ok(Some(value.clone())),
+    _ => Err(format!("{} must be string", key)),
+  }
+}
+
+fn check_keys(yaml: &Yaml, table: &str, keys: &[&str]) -> Result<(), String> {
+  for key in yaml.as_hash().into_iter().flat_map(|hash| hash.keys()) {
+    let key = key.as_str().unwrap_or("");
+    if !keys.contains(&key) {
+      return Err(format!("unknown key in {}: {}", table, key));
+    }
+  }
+  Ok(())
+}
+
+// `keys` are those the target supports besides `types` and `naming`
+fn target_settings(
+  yaml: &Yaml,
+  table: &str,
+  keys: &[&str],
+  dir: &Path,
+) -> Result<TargetSettings, String> {
+  let keys = keys
+    .iter()
+    .chain(["types", "naming"].iter())
+    .cloned()
+    .collect::<Vec<_>>();
+  check_keys(yaml, table, &keys)?;
+  let mut settings = TargetSettings {
+    package: string(&yaml["package"], &format!("{}.package", table))?,
+    ..TargetSettings::default()
+  };
+  for (kind, type_name) in yaml["types"].as_hash().into_iter().flatten() {
+    let kind = kind.as_str().unwrap_or("");
+    let type_name = string(type_name, &format!("{}.types.{}", table, kind))?;
+    settings
+      .types
+      .insert(kind.to_string(), type_name.unwrap_or_default());
+  }
+  if let Some(templates) = string(&yaml["templates"], &format!("{}.templates", table))? {
+    settings.templates = load_templates(&dir.join(templates))?;
+  }
+  if let Some(name) = string(&yaml["naming"], &format!("{}.naming", table))? {
+    settings.naming = Some(Naming::parse(&name).ok_or_else(|| {
+      format!(
+        "{}.naming must be one of {}",
+        table,
+        Naming::NAMES.join(", ")
+      )
+    })?);
+  }
+  Ok(settings)
+}
+
+/// Reads the `command.hbs`, `viewmodel.hbs` and `response.hbs` found in `dir`, written in the
+/// Handlebars subset of `template`; file types without one keep the built-in template.
+pub fn load_templates(dir: &Path) -> Result<HashMap<String, Template>, String> {
+  if !dir.is_dir() {
+    return Err(format!("{}: templates directory not found", dir.display()));
+  }
+  let mut templates = HashMap::new();
+  for file_type in TEMPLATE_FILE_TYPES.iter() {
+    let path = dir.join(format!("{}.hbs", file_type));
+    if let Ok(source) = fs::read_to_string(&path) {
+      let template =
+        Template::parse(&source).map_err(|err| format!("{}: {}", path.display(), err))?;
+      templates.insert(file_type.to_string(), template);
+    }
+  }
+  Ok(templates)
+}
+
+/// Writes the built-in templates to `<dir>/scala` and `<dir>/typescript`, as a starting point
+/// for the `templates` setting of those targets.
+pub fn write_default_templates(dir: &Path) -> io::Result<()> {
+  for (target, source) in [
+    ("scala", apis::SCALA_TEMPLATE),
+    ("typescript", apis::TYPESCRIPT_TEMPLATE),
+  ] {
+    fs::create_dir_all(dir.join(target))?;
+    for file_type in TEMPLATE_FILE_TYPES.iter() {
+      fs::write(dir.join(target).join(format!("{}.hbs", file_type)), source)?;
+    }
+  }
+  Ok(())
+}
+
+impl Config {
+  pub fn enabled(&self, target: &str) -> bool {
+    self.targets.iter().any(|enabled| enabled == target)
+  }
+
+  fn set_targets(&mut self, targets: Vec<String>) -> Result<(), String> {
+    if let Some(unknown) = targets.iter().find(|t| !TARGETS.contains(&&t[..])) {
+      return Err(format!(
+        "unknown target: {} (expected one of {})",
+        unknown,
+        TARGETS.join(", ")
+      ));
+    }
+    self.targets = targets;
+    Ok(())
+  }
+
+  /// Reads a parsed `draco.toml` such as
+  ///
+  /// ```toml
+  /// input = "openapi.yaml"
+  /// output = "generated"
+  /// targets = ["scala", "typescript"]
+  ///
+  /// [scala]
+  /// package = "com.example.api"
+  /// templates = "templates/scala"
+  /// naming = "camelCase"
+  ///
+  /// [scala.types]
+  /// integer = "Long"
+  /// "command.date" = "LocalDate"
+  ///
+  /// [go]
+  /// naming = "PascalCase"
+  ///
+  /// [go.types]
+  /// date = "string"
+  /// ```
+  ///
+  /// `typescript`, `rust`, `python`, `go`, `java` and `elm` take `types` and `naming` too;
+  /// `typescript` also takes `templates`, `python` takes `style` (`pydantic` or `dataclass`)
+  /// and `enums` (`literal` or `enum`), `java` takes `package` and `style` (`record` or `pojo`).
+  /// Relative `input`, `output` and `templates` paths are resolved against `dir`, the directory
+  /// of the config file.
+  pub fn from_yaml(yaml: &Yaml, dir: &Path) -> Result<Config, String> {
+    check_keys(
+      yaml,
+      "draco.toml",
+      &[
+        "input",
+        "output",
+        "targets",
+        "scala",
+        "typescript",
+        "rust",
+        "python",
+        "go",
+        "java",
+        "elm",
+        "examples",
+      ],
+    )?;
+    let resolve = |path: String| dir.join(path).to_string_lossy().into_owned();
+    let mut config = Config {
+      input: string(&yaml["input"], "input")?.map(resolve),
+      ..Config::default()
+    };
+    if let Some(output) = string(&yaml["output"], "output")? {
+      config.output = resolve(output);
+    }
+    if let Some(targets) = yaml["targets"].as_vec() {
+      config.set_targets(
+        targets
+          .iter()
+          .map(|target| target.as_str().unwrap_or("").to_string())
+          .collect(),
+      )?;
+    }
+    config.scala = target_settings(&yaml["scala"], "scala", &["package", "templates"], dir)?;
+    config.typescript = target_settings(&yaml["typescript"], "typescript", &["templates"], dir)?;
+    config.rust = target_settings(&yaml["rust"], "rust", &[], dir)?;
+    config.python = target_settings(&yaml["python"], "python", &["style", "enums"], dir)?;
+    config.go = target_settings(&yaml["go"], "go", &[], dir)?;
+    config.java = target_settings(&yaml["java"], "java", &["package", "style"], dir)?;
+    config.elm = target_settings(&yaml["elm"], "elm", &[], dir)?;
+
+    config.python_style = match string(&yaml["python"]["style"], "python.style")?.as_deref() {
+      None | Some("pydantic") => PythonStyle::Pydantic,
+      Some("dataclass") => PythonStyle::Dataclass,
+      Some(style) => return Err(format!("unknown python style: {}", style)),
+    };
+    config.python_enums = match string(&yaml["python"]["enums"], "python.enums")?.as_deref() {
+      None | Some("literal") => PythonEnums::Literal,
+      Some("enum") => PythonEnums::Enum,
+      Some(enums) => return Err(format!("unknown python enums: {}", enums)),
+    };
+    config.java_style = match string(&yaml["java"]["style"], "java.style")?.as_deref() {
+      None | Some("record") => JavaStyle::Record,
+      Some("pojo") => JavaStyle::Pojo,
+      Some(style) => return Err(format!("unknown java style: {}", style)),
+    };
+    check_keys(&yaml["examples"], "examples", &["nulls", "seed"])?;
+    if let Some(nulls) = yaml["examples"]["nulls"].as_bool() {
+      config.examples.nulls = nulls;
+    }
+    if let Some(seed) = yaml["examples"]["seed"].as_i64() {
+      config.examples.seed = seed as u64;
+    }
+    Ok(config)
+  }
+
+  /// Reads `--config <path>`, or `draco.toml` when it exists in the working directory, and
+  /// applies the command line over it.
+  pub fn load(args: &[String]) -> Result<Config, String> {
+    let value_of = |flag: &str| {
+      args
+        .iter()
+        .position(|arg| arg == flag)
+        .map(|index| {
+          args
+            .get(index + 1)
+            .cloned()
+            .ok_or_else(|| format!("{} needs a value", flag))
+        })
+        .transpose()
+    };
+    let read = |path: &str| {
+      let source = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+      let dir = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+      parse_toml(&source)
+        .and_then(|yaml| Config::from_yaml(&yaml, dir))
+        .map_err(|err| format!("{}: {}", path, err))
+    };
+    let mut config = match value_of("--config")? {
+      Some(path) => read(&path)?,
+      None if Path::new("draco.toml").exists() => read("draco.toml")?,
+      None => Config::default(),
+    };
+
+    let mut skip_value = false;
+    for arg in args {
+      if skip_value {
+        skip_value = false;
+      } else if arg == "--config" || arg == "--output" || arg == "--targets" {
+        skip_value = true;
+      } else if !arg.starts_with("--") {
+        config.input = Some(arg.clone());
+        break;
+      }
+    }
+    if let Some(output) = value_of("--output")? {
+      config.output = output;
+    }
+    if let Some(targets) = value_of("--targets")? {
+      config.set_targets(targets.split(',').map(|t| t.trim().to_string()).collect())?;
+    }
+    if args.iter().any(|arg| arg == "--python-dataclass") {
+      config.python_style = PythonStyle::Dataclass;
+    }
+    if args.iter().any(|arg| arg == "--python-enum") {
+      config.python_enums = PythonEnums::Enum;
+    }
+    if args.iter().any(|arg| arg == "--java-pojo") {
+      config.java_style = JavaStyle::Pojo;
+    }
+    if args.iter().any(|arg| arg == "--example-nulls") {
+      config.examples.nulls = true;
+    }
+    check_output(&config.output, config.input.as_deref())?;
+    Ok(config)
+  }
+}
+
+/// Generation replaces files in the output directory, so it must not be a directory holding
+/// the spec or the working directory, like `.` or `..`.
+pub fn check_output(output: &str, input: Option<&str>) -> Result<(), String> {
+  let output_path = match Path::new(output).canonicalize() {
+    Ok(path) => path,
+    // created by the generation
+    Err(_) => return Ok(()),
+  };
+  let contains = |path: &Path| {
+    path
+      .canonicalize()
+      .map(|path| path.starts_with(&output_path))
+      .unwrap_or(false)
+  };
+  if std::env::current_dir()
+    .map(|cwd| contains(&cwd))
+    .unwrap_or(true)
+  {
+    return Err(format!(
+      "output {} contains the working directory, choose a dedicated directory",
+      output
+    ));
+  }
+  match input {
+    Some(input) if input != "-" && contains(Path::new(input)) => Err(format!(
+      "output {} contains the input {}, choose a dedicated directory",
+      output, input
+    )),
+    _ => Ok(()),
+  }
+}
+
+fn from_toml(value: toml::Value) -> Yaml {
+  match value {
+    toml::Value::String(value) => Yaml::String(value),
+    toml::Value::Integer(value) => Yaml::Integer(value),
+    toml::Value::Float(value) => Yaml::Real(value.to_string()),
+    toml::Value::Boolean(value) => Yaml::Boolean(value),
+    toml::Value::Datetime(value) => Yaml::String(value.to_string()),
+    toml::Value::Array(values) => Yaml::Array(values.into_iter().map(from_toml).collect()),
+    toml::Value::Table(table) => Yaml::Hash(
+      table
+        .into_iter()
+        .map(|(key, value)| (Yaml::String(key), from_toml(value)))
+        .collect(),
+    ),
+  }
+}
+
+/// Parses a config file into `Yaml`, which the config reads like the specs.
+pub fn parse_toml(source: &str) -> Result<Yaml, String> {
+  toml::from_str(source)
+    .map(from_toml)
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_config_from_toml() {
+    let toml = r#"
+# generator settings
+input = "openapi.yaml"
+targets = [
+  "scala",
+  'typescript', # clients
+  "go",
+]
+
+[scala]
+package = "com.example.api"
+naming = "key"
+
+[scala.types]
+integer = "Long" # no more placeholders
+"command.date" = "LocalDate"
+
+[go.types]
+date = "string"
+
+[elm]
+naming = "snake_case"
+
+[python]
+enums = "enum"
+
+[examples]
+nulls = true
+seed = 1_000
+"#;
+    let config = Config::from_yaml(&parse_toml(toml).unwrap(), Path::new("")).unwrap();
+    assert_eq!(Some("openapi.yaml".to_string()), config.input);
+    assert_eq!("dist", config.output);
+    assert!(config.enabled("typescript") && !config.enabled("rust"));
+    assert_eq!(Some("com.example.api".to_string()), config.scala.package);
+    assert_eq!(Some(Naming::Key), config.scala.naming);
+    assert_eq!(Some(&"string".to_string()), config.go.types.get("date"));
+    assert_eq!(Some(Naming::SnakeCase), config.elm.naming);
+    assert_eq!(PythonEnums::Enum, config.python_enums);
+    assert_eq!(
+      Some(&"LocalDate".to_string()),
+      config.scala.types.get("command.date")
+    );
+    assert_eq!(
+      sample::Options {
+        seed: 1000,
+        nulls: true
+      },
+      config.examples
+    );
+
+    assert_eq!(
+      Err("unknown key in scala: pakage".to_string()),
+      Config::from_yaml(
+        &parse_toml("[scala]\npakage = \"x\"").unwrap(),
+        Path::new("")
+      )
+    );
+    let from_toml = |source: &str| Config::from_yaml(&parse_toml(source)?, Path::new("api"));
+    assert!(from_toml("[typescript]\nnaming = \"kebab\"").is_err());
+    assert_eq!(
+      Err("unknown key in typescript: package".to_string()),
+      from_toml("[typescript]\npackage = \"x\"")
+    );
+    let config = from_toml("input = \"openapi.yaml\"\noutput = \"/tmp/dist\"").unwrap();
+    assert_eq!(
+      Some(
+        Path::new("api")
+          .join("openapi.yaml")
+          .to_string_lossy()
+          .into_owned()
+      ),
+      config.input
+    );
+    assert_eq!("/tmp/dist", config.output);
+    assert!(check_output(".", None).is_err());
+    assert!(check_output("..", None).is_err());
+    let dir = crate::TestDir::new("it-config-from-toml");
+    let input = dir.join("openapi.yaml");
+    fs::write(&input, "openapi: 3.0.0").unwrap();
+    assert!(check_output(&dir.to_string_lossy(), input.to_str()).is_err());
+    assert_eq!(
+      Ok(()),
+      check_output(&dir.join("dist").to_string_lossy(), input.to_str())
+    );
+
+    assert_eq!(
+      Err("expected an equals, found a string at line 2 column 8".to_string()),
+      parse_toml("input = \"a\"\noutput \"b\"")
+    );
+    assert_eq!(
+      Err("expected an equals, found a string at line 4 column 8".to_string()),
+      parse_toml("targets = [\n\"rust\",\n]\noutput \"b\"")
+    );
+    assert!(parse_toml("[scala]\npackage = \"a\"\n[scala]\nnaming = \"key\"").is_err());
+  }
+}
@@ -0,0 +1,567 @@
+use crate::apis::{Api, Body, Content, Method, ParamType, Property};
+use yaml_rust::yaml::Hash;
+use yaml_rust::Yaml;
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Change {
+  pub kind: String,
+  pub breaking: bool,
+  pub location: String,
+  pub message: String,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Direction {
+  Request,
+  Response,
+}
+
+fn change(kind: &str, breaking: bool, location: &str, message: String) -> Change {
+  Change {
+    kind: kind.to_string(),
+    breaking,
+    location: location.to_string(),
+    message,
+  }
+}
+
+/// Compares two versions of a spec. A change is breaking when a client written against the
+/// old one can fail with the new one, e.g. a required property added to a request.
+pub fn diff(old: &[Api], new: &[Api]) -> Vec<Change> {
+  let mut changes = vec![];
+  for old_api in old {
+    match new.iter().find(|api| api.path == old_api.path) {
+      None => changes.push(change(
+        "removed-endpoint",
+        true,
+        &old_api.path,
+        "endpoint removed".to_string(),
+      )),
+      Some(new_api) => diff_api(old_api, new_api, &mut changes),
+    }
+  }
+  for new_api in new {
+    if !old.iter().any(|api| api.path == new_api.path) {
+      changes.push(change(
+        "added-endpoint",
+        false,
+        &new_api.path,
+        "endpoint added".to_string(),
+      ));
+    }
+  }
+  changes
+}
+
+fn diff_api(old: &Api, new: &Api, changes: &mut Vec<Change>) {
+  let param_type = |param_type: &ParamType| match param_type {
+    ParamType::Integer => "integer",
+    ParamType::String => "string",
+  };
+  for (param, old_type) in old.param_map.iter() {
+    match new.param_map.get(param) {
+      None => changes.push(change(
+        "removed-path-parameter",
+        true,
+        &old.path,
+        format!("path parameter '{}' removed", param),
+      )),
+      Some(new_type) if new_type != old_type => changes.push(change(
+        "retyped-path-parameter",
+        true,
+        &old.path,
+        format!(
+          "path parameter '{}' changed from {} to {}",
+          param,
+          param_type(old_type),
+          param_type(new_type)
+        ),
+      )),
+      _ => {}
+    }
+  }
+  for param in new.param_map.keys() {
+    if !old.param_map.contains_key(param) {
+      changes.push(change(
+        "added-path-parameter",
+        true,
+        &old.path,
+        format!("path parameter '{}' added", param),
+      ));
+    }
+  }
+
+  for (method, old_method) in old.method_map.iter() {
+    let location = format!("{} {}", method.to_uppercase(), old.path);
+    match new.method_map.get(method) {
+      None => changes.push(change(
+        "removed-method",
+        true,
+        &location,
+        "method removed".to_string(),
+      )),
+      Some(new_method) => diff_method(old_method, new_method, &location, changes),
+    }
+  }
+  for method in new.method_map.keys() {
+    if !old.method_map.contains_key(method) {
+      changes.push(change(
+        "added-method",
+        false,
+        &format!("{} {}", method.to_uppercase(), new.path),
+        "method added".to_string(),
+      ));
+    }
+  }
+}
+
+fn diff_method(old: &Method, new: &Method, location: &str, changes: &mut Vec<Change>) {
+  diff_body(
+    &old.request_body_opt,
+    &new.request_body_opt,
+    &format!("{} request", location),
+    Direction::Request,
+    changes,
+  );
+  let content = |body: &Option<Body>| body.as_ref().map(|body| body.content.clone());
+  for (status, old_body) in old.response_map.iter() {
+    let response_location = format!("{} {} response", location, status);
+    match new.response_map.get(status) {
+      None => changes.push(change(
+        "removed-response",
+        true,
+        &response_location,
+        "response removed".to_string(),
+      )),
+      Some(new_body) => diff_body(
+        &content(old_body),
+        &content(new_body),
+        &response_location,
+        Direction::Response,
+        changes,
+      ),
+    }
+  }
+  for status in new.response_map.keys() {
+    if !old.response_map.contains_key(status) {
+      changes.push(change(
+        "added-response",
+        false,
+        &format!("{} {} response", location, status),
+        "response added".to_string(),
+      ));
+    }
+  }
+}
+
+fn diff_body(
+  old: &Option<Content>,
+  new: &Option<Content>,
+  location: &str,
+  direction: Direction,
+  changes: &mut Vec<Change>,
+) {
+  match (old, new) {
+    (Some(old), Some(new)) => diff_content(old, new, location, direction, changes),
+    (Some(_), None) => changes.push(change(
+      "removed-body",
+      direction == Direction::Response,
+      location,
+      "body removed".to_string(),
+    )),
+    (None, Some(_)) => changes.push(change(
+      "added-body",
+      direction == Direction::Request,
+      location,
+      "body added".to_string(),
+    )),
+    (None, None) => {}
+  }
+}
+
+fn type_name(content: &Content) -> String {
+  match content {
+    Content::Array(content) => format!("array of {}", type_name(content)),
+    Content::Object(_) => "object".to_string(),
+    Content::String => "string".to_string(),
+    Content::Integer => "integer".to_string(),
+    Content::Number => "number".to_string(),
+    Content::Boolean => "boolean".to_string(),
+    Content::Date => "date".to_string(),
+    Content::Binary => "binary".to_string(),
+    Content::Enum(_) => "enum".to_string(),
+    Content::Const(value) => format!("const {}", crate::loader::to_json(value)),
+    Content::Union(_) => "union".to_string(),
+    Content::Tuple(_) => "tuple".to_string(),
+  }
+}
+
+fn diff_content(
+  old: &Content,
+  new: &Content,
+  location: &str,
+  direction: Direction,
+  changes: &mut Vec<Change>,
+) {
+  match (old, new) {
+    (Content::Object(old_properties), Content::Object(new_properties)) => {
+      diff_properties(old_properties, new_properties, location, direction, changes)
+    }
+    (Content::Array(old_items), Content::Array(new_items)) => diff_content(
+      old_items,
+      new_items,
+      &format!("{}[]", location),
+      direction,
+      changes,
+    ),
+    (Content::Enum(old_values), Content::Enum(new_values)) => {
+      for value in old_values
+        .iter()
+        .filter(|value| !new_values.contains(value))
+      {
+        changes.push(change(
+          "removed-enum-value",
+          true,
+          location,
+          format!("enum value '{}' removed", value),
+        ));
+      }
+      for value in new_values
+        .iter()
+        .filter(|value| !old_values.contains(value))
+      {
+        changes.push(change(
+          "added-enum-value",
+          false,
+          location,
+          format!("enum value '{}' added", value),
+        ));
+      }
+    }
+    (old, new) if old != new => changes.push(change(
+      "retyped",
+      true,
+      location,
+      format!("type changed from {} to {}", type_name(old), type_name(new)),
+    )),
+    _ => {}
+  }
+}
+
+fn diff_properties(
+  old: &[Property],
+  new: &[Property],
+  location: &str,
+  direction: Direction,
+  changes: &mut Vec<Change>,
+) {
+  for old_property in old {
+    let property_location = format!("{}.{}", location, old_property.key);
+    match new.iter().find(|property| property.key == old_property.key) {
+      None => changes.push(change(
+        "removed-property",
+        direction == Direction::Response,
+        &property_location,
+        "property removed".to_string(),
+      )),
+      Some(new_property) => {
+        if !old_property.or_null && new_property.or_null {
+          changes.push(change(
+            "became-nullable",
+            direction == Direction::Response,
+            &property_location,
+            "property became nullable".to_string(),
+          ));
+        }
+        if old_property.or_null && !new_property.or_null {
+          changes.push(change(
+            "became-non-nullable",
+            direction == Direction::Request,
+            &property_location,
+            "property became non-nullable".to_string(),
+          ));
+        }
+        if !old_property.required && new_property.required {
+          changes.push(change(
+            "became-required",
+            direction == Direction::Request,
+            &property_location,
+            "property became required".to_string(),
+          ));
+        }
+        if old_property.required && !new_property.required {
+          changes.push(change(
+            "became-optional",
+            direction == Direction::Response,
+            &property_location,
+            "property became optional".to_string(),
+          ));
+        }
+        diff_content(
+          &old_property.value,
+          &new_property.value,
+          &property_location,
+          direction,
+          changes,
+        );
+      }
+    }
+  }
+  for new_property in new {
+    if !old.iter().any(|property| property.key == new_property.key) {
+      changes.push(change(
+        "added-property",
+        direction == Direction::Request && new_property.required,
+        &format!("{}.{}", location, new_property.key),
+        "property added".to_string(),
+      ));
+    }
+  }
+}
+
+pub fn to_report(changes: &[Change]) -> String {
+  let breaking = changes.iter().filter(|change| change.breaking).count();
+  changes
+    .iter()
+    .map(|change| {
+      format!(
+        "{:9} {} {}: {}\n",
+        if change.breaking { "BREAKING" } else { "" },
+        change.kind,
+        change.location,
+        change.message
+      )
+    })
+    .collect::<String>()
+    + &format!(
+      "{} breaking changes, {} non-breaking changes\n",
+      breaking,
+      changes.len() - breaking
+    )
+}
+
+pub fn to_json(changes: &[Change]) -> String {
+  let breaking = changes.iter().filter(|change| change.breaking).count();
+  let mut summary = Hash::new();
+  summary.insert(
+    Yaml::String("breaking".to_string()),
+    Yaml::Integer(breaking as i64),
+  );
+  summary.insert(
+    Yaml::String("nonBreaking".to_string()),
+    Yaml::Integer((changes.len() - breaking) as i64),
+  );
+  summary.insert(
+    Yaml::String("changes".to_string()),
+    Yaml::Array(
+      changes
+        .iter()
+        .map(|change| {
+          let mut entry = Hash::new();
+          entry.insert(
+            Yaml::String("kind".to_string()),
+            Yaml::String(change.kind.clone()),
+          );
+          entry.insert(
+            Yaml::String("breaking".to_string()),
+            Yaml::Boolean(change.breaking),
+          );
+          entry.insert(
+            Yaml::String("location".to_string()),
+            Yaml::String(change.location.clone()),
+          );
+          entry.insert(
+            Yaml::String("message".to_string()),
+            Yaml::String(change.message.clone()),
+          );
+          Yaml::Hash(entry)
+        })
+        .collect(),
+    ),
+  );
+  crate::loader::to_json(&Yaml::Hash(summary))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::apis::from_yaml;
+  use yaml_rust::YamlLoader;
+
+  #[test]
+  fn it_diff() {
+    let old = "
+      paths:
+        '/users/{userId}':
+          parameters:
+            - name: userId
+              in: path
+              schema:
+                type: integer
+          get:
+            summary: ユーザ詳細GET
+            operationId: get-users-userId
+            responses:
+              '200':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      required: [name]
+                      properties:
+                        name:
+                          type: string
+                        age:
+                          type: integer
+                        rank:
+                          type: string
+                          enum: [S, A]
+              '404':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        message:
+                          type: string
+                        code:
+                          type: integer
+          put:
+            summary: ユーザ詳細PUT
+            operationId: put-users-userId
+            requestBody:
+              content:
+                application/json:
+                  schema:
+                    type: object
+                    properties:
+                      memo:
+                        type: [string, 'null']
+            responses:
+              '204':
+                description: No Content
+              '409':
+                content:
+                  application/json:
+                    schema:
+                      type: string
+          delete:
+            summary: ユーザ削除
+            operationId: delete-users-userId
+      ";
+    let new = "
+      paths:
+        '/users/{userId}':
+          parameters:
+            - name: userId
+              in: path
+              schema:
+                type: string
+          get:
+            summary: ユーザ詳細GET
+            operationId: get-users-userId
+            responses:
+              '200':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        name:
+                          type: [string, 'null']
+                        rank:
+                          type: string
+                          enum: [A, B]
+                        email:
+                          type: string
+              '404':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        message:
+                          type: string
+          put:
+            summary: ユーザ詳細PUT
+            operationId: put-users-userId
+            requestBody:
+              content:
+                application/json:
+                  schema:
+                    type: object
+                    required: [memo, code]
+                    properties:
+                      memo:
+                        type: string
+                      code:
+                        type: string
+                      tag:
+                        type: string
+            responses:
+              '204':
+                description: No Content
+              '422':
+                description: Unprocessable Entity
+      ";
+    let parse = |yaml: &str| from_yaml(&YamlLoader::load_from_str(yaml).unwrap()[0]);
+    let changes = diff(&parse(old), &parse(new));
+
+    assert_eq!(
+      vec![
+        ("retyped-path-parameter", true, "/users/{userId}"),
+        (
+          "became-nullable",
+          true,
+          "GET /users/{userId} 200 response.name"
+        ),
+        (
+          "became-optional",
+          true,
+          "GET /users/{userId} 200 response.name"
+        ),
+        (
+          "removed-property",
+          true,
+          "GET /users/{userId} 200 response.age"
+        ),
+        (
+          "removed-enum-value",
+          true,
+          "GET /users/{userId} 200 response.rank"
+        ),
+        (
+          "added-enum-value",
+          false,
+          "GET /users/{userId} 200 response.rank"
+        ),
+        (
+          "added-property",
+          false,
+          "GET /users/{userId} 200 response.email"
+        ),
+        (
+          "removed-property",
+          true,
+          "GET /users/{userId} 404 response.code"
+        ),
+        (
+          "became-non-nullable",
+          true,
+          "PUT /users/{userId} request.memo"
+        ),
+        ("became-required", true, "PUT /users/{userId} request.memo"),
+        ("added-property", true, "PUT /users/{userId} request.code"),
+        ("added-property", false, "PUT /users/{userId} request.tag"),
+        ("removed-response", true, "PUT /users/{userId} 409 response"),
+        ("added-response", false, "PUT /users/{userId} 422 response"),
+        ("removed-method", true, "DELETE /users/{userId}"),
+      ],
+      changes
+        .iter()
+        .map(|change| (&change.kind[..], change.breaking, &change.location[..]))
+        .collect::<Vec<_>>()
+    );
+    assert!(to_json(&changes).starts_with("{\n  \"breaking\": 11,\n  \"nonBreaking\": 4,"));
+  }
+}
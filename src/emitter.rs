@@ -0,0 +1,820 @@
+//! Writes OpenAPI documents: `normalize` tidies a bundled spec without dropping anything, and
+//! `to_openapi` writes the model back, the inverse of `apis::from_yaml`.
+//!
+//! Both sort keys, except where the order carries meaning like `properties`, and fill in required
+//! fields such as response descriptions, so the output is a normalized, self-contained spec.
+
+use crate::apis::{Api, Constraints, Content, Docs, Method, ParamType, Property, SpecVersion};
+use yaml_rust::yaml::Hash;
+use yaml_rust::{Yaml, YamlEmitter};
+
+const OPERATIONS: [&str; 8] = [
+  "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+fn key(str: &str) -> Yaml {
+  Yaml::String(str.to_string())
+}
+
+fn hash(entries: Vec<(&str, Yaml)>) -> Yaml {
+  let mut hash = Hash::new();
+  for (name, value) in entries {
+    if !value.is_badvalue() {
+      hash.insert(key(name), value);
+    }
+  }
+  Yaml::Hash(hash)
+}
+
+fn optional<T>(value: &Option<T>, to_yaml: impl Fn(&T) -> Yaml) -> Yaml {
+  value.as_ref().map(to_yaml).unwrap_or(Yaml::BadValue)
+}
+
+fn flag(value: bool) -> Yaml {
+  if value {
+    Yaml::Boolean(true)
+  } else {
+    Yaml::BadValue
+  }
+}
+
+fn number(value: &f64) -> Yaml {
+  if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+    Yaml::Integer(*value as i64)
+  } else {
+    Yaml::Real(value.to_string())
+  }
+}
+
+fn version_of(document: &Yaml) -> SpecVersion {
+  match document["openapi"].as_str() {
+    Some(openapi) if openapi.starts_with("3.0") => SpecVersion::V30,
+    _ => SpecVersion::V31,
+  }
+}
+
+fn docs_entries(docs: &Docs) -> Vec<(&'static str, Yaml)> {
+  vec![
+    ("title", optional(&docs.title, |title| key(title))),
+    (
+      "description",
+      optional(&docs.description, |description| key(description)),
+    ),
+    ("deprecated", flag(docs.deprecated)),
+    (
+      "example",
+      optional(&docs.example, |example| example.clone()),
+    ),
+    (
+      "externalDocs",
+      optional(&docs.external_docs, |external_docs| {
+        hash(vec![
+          ("url", key(&external_docs.url)),
+          (
+            "description",
+            optional(&external_docs.description, |description| key(description)),
+          ),
+        ])
+      }),
+    ),
+  ]
+}
+
+fn constraints_entries(constraints: &Constraints) -> Vec<(&'static str, Yaml)> {
+  let count = |value: &u64| Yaml::Integer(*value as i64);
+  vec![
+    ("minimum", optional(&constraints.minimum, number)),
+    ("maximum", optional(&constraints.maximum, number)),
+    ("multipleOf", optional(&constraints.multiple_of, number)),
+    ("minLength", optional(&constraints.min_length, count)),
+    ("maxLength", optional(&constraints.max_length, count)),
+    (
+      "pattern",
+      optional(&constraints.pattern, |pattern| key(pattern)),
+    ),
+    ("minItems", optional(&constraints.min_items, count)),
+    ("maxItems", optional(&constraints.max_items, count)),
+    ("uniqueItems", flag(constraints.unique_items)),
+  ]
+}
+
+// members without keywords of their own, which a 3.1 type array can hold together
+fn is_type_array(contents: &[Content]) -> bool {
+  contents.iter().all(|content| {
+    matches!(
+      content,
+      Content::String | Content::Integer | Content::Number | Content::Boolean
+    )
+  })
+}
+
+// the `type` of a content and the keywords belonging to it; only unions of keyword-free
+// members get here, any other union is written as `anyOf` by `schema_with`
+fn typed_entries(
+  content: &Content,
+  version: SpecVersion,
+) -> (Vec<Yaml>, Vec<(&'static str, Yaml)>) {
+  match content {
+    Content::Object(properties) => {
+      let required = properties
+        .iter()
+        .filter(|property| property.required)
+        .map(|property| key(&property.key))
+        .collect::<Vec<_>>();
+      (
+        vec![key("object")],
+        vec![
+          (
+            "properties",
+            Yaml::Hash(
+              properties
+                .iter()
+                .map(|property| (key(&property.key), property_schema(property, version)))
+                .collect(),
+            ),
+          ),
+          (
+            "required",
+            if required.is_empty() {
+              Yaml::BadValue
+            } else {
+              Yaml::Array(required)
+            },
+          ),
+        ],
+      )
+    }
+    Content::Array(items) => (vec![key("array")], vec![("items", schema(items, version))]),
+    Content::Tuple(items) => (
+      vec![key("array")],
+      vec![(
+        "prefixItems",
+        Yaml::Array(items.iter().map(|item| schema(item, version)).collect()),
+      )],
+    ),
+    Content::String => (vec![key("string")], vec![]),
+    Content::Date => (vec![key("string")], vec![("format", key("date"))]),
+    Content::Binary => (vec![key("string")], vec![("format", key("binary"))]),
+    Content::Enum(values) => (
+      vec![key("string")],
+      vec![(
+        "enum",
+        Yaml::Array(values.iter().map(|value| key(value)).collect()),
+      )],
+    ),
+    // 3.0 has no `const`, a single value enum means the same
+    Content::Const(value) => (
+      typed_entries(&Content::const_type(value), version).0,
+      vec![match version {
+        SpecVersion::V30 => ("enum", Yaml::Array(vec![value.clone()])),
+        SpecVersion::V31 => ("const", value.clone()),
+      }],
+    ),
+    Content::Integer => (vec![key("integer")], vec![]),
+    Content::Number => (vec![key("number")], vec![]),
+    Content::Boolean => (vec![key("boolean")], vec![]),
+    Content::Union(contents) => (
+      contents
+        .iter()
+        .flat_map(|content| typed_entries(content, version).0)
+        .collect(),
+      vec![],
+    ),
+  }
+}
+
+// 3.0 marks nulls with `nullable`, 3.1 with a 'null' type; 3.0 has no type arrays at all
+fn schema_with(
+  content: &Content,
+  or_null: bool,
+  extra: Vec<(&str, Yaml)>,
+  version: SpecVersion,
+) -> Yaml {
+  let nullable = ("nullable", flag(or_null && version == SpecVersion::V30));
+  let mut entries = match content {
+    Content::Union(contents) if version == SpecVersion::V30 || !is_type_array(contents) => {
+      let mut members = contents
+        .iter()
+        .map(|content| schema(content, version))
+        .collect::<Vec<_>>();
+      if or_null && version == SpecVersion::V31 {
+        members.push(hash(vec![("type", key("null"))]));
+      }
+      vec![("anyOf", Yaml::Array(members)), nullable]
+    }
+    // a null type would not get past the `const`
+    Content::Const(_) if or_null && version == SpecVersion::V31 => vec![(
+      "anyOf",
+      Yaml::Array(vec![
+        schema(content, version),
+        hash(vec![("type", key("null"))]),
+      ]),
+    )],
+    _ => {
+      let (mut types, mut typed) = typed_entries(content, version);
+      // nor past an `enum` without null
+      for (name, value) in typed.iter_mut() {
+        if let (true, "enum", Yaml::Array(values)) = (or_null, *name, value) {
+          values.push(Yaml::Null);
+        }
+      }
+      if or_null && version == SpecVersion::V31 {
+        types.push(key("null"));
+      }
+      let schema_type = if types.len() == 1 {
+        types.remove(0)
+      } else {
+        Yaml::Array(types)
+      };
+      let mut entries = vec![("type", schema_type), nullable];
+      entries.extend(typed);
+      entries
+    }
+  };
+  entries.extend(extra);
+  hash(entries)
+}
+
+fn schema(content: &Content, version: SpecVersion) -> Yaml {
+  schema_with(content, false, vec![], version)
+}
+
+fn property_schema(property: &Property, version: SpecVersion) -> Yaml {
+  let mut extra = constraints_entries(&property.constraints);
+  extra.extend(docs_entries(&property.docs));
+  schema_with(&property.value, property.or_null, extra, version)
+}
+
+fn content(media_type: &str, content: &Content, version: SpecVersion) -> Yaml {
+  let mut media = Hash::new();
+  media.insert(
+    key(media_type),
+    hash(vec![("schema", schema(content, version))]),
+  );
+  Yaml::Hash(media)
+}
+
+fn response_description(status: &str) -> Yaml {
+  key(&format!("{} response", status))
+}
+
+fn operation(method: &Method, version: SpecVersion) -> Yaml {
+  let request_body = match (&method.request_body_opt, &method.request_media_type) {
+    (Some(request_body), Some(media_type)) => hash(vec![(
+      "content",
+      content(media_type, request_body, version),
+    )]),
+    (Some(request_body), None) => hash(vec![(
+      "content",
+      content("application/json", request_body, version),
+    )]),
+    _ => Yaml::BadValue,
+  };
+  let responses = method
+    .response_map
+    .iter()
+    .map(|(status, body)| {
+      (
+        key(status),
+        hash(vec![
+          ("description", response_description(status)),
+          (
+            "content",
+            optional(body, |body| {
+              content(&body.media_type, &body.content, version)
+            }),
+          ),
+        ]),
+      )
+    })
+    .collect::<Hash>();
+  let mut entries = vec![
+    ("operationId", key(&method.operation_id)),
+    ("summary", key(&method.summary)),
+  ];
+  entries.extend(docs_entries(&method.docs));
+  entries.push(("requestBody", request_body));
+  entries.push(("responses", Yaml::Hash(responses)));
+  hash(entries)
+}
+
+fn path_item(api: &Api, version: SpecVersion) -> Yaml {
+  let parameters = api
+    .param_map
+    .iter()
+    .map(|(name, param_type)| {
+      hash(vec![
+        ("name", key(name)),
+        ("in", key("path")),
+        ("required", Yaml::Boolean(true)),
+        (
+          "schema",
+          hash(vec![(
+            "type",
+            key(match param_type {
+              ParamType::Integer => "integer",
+              ParamType::String => "string",
+            }),
+          )]),
+        ),
+      ])
+    })
+    .collect::<Vec<_>>();
+  let mut item = Hash::new();
+  if !parameters.is_empty() {
+    item.insert(key("parameters"), Yaml::Array(parameters));
+  }
+  for (method_type, method) in api.method_map.iter() {
+    item.insert(key(method_type), operation(method, version));
+  }
+  Yaml::Hash(item)
+}
+
+fn default_info() -> Yaml {
+  hash(vec![("title", key("API")), ("version", key("0.0.0"))])
+}
+
+/// Only what the generators see: the paths with their path parameters and the schemas of the
+/// chosen bodies. Query and header parameters, `servers`, `tags`, `security`, `components` and
+/// response descriptions are not in the model; `normalize` keeps them. The version and `info`
+/// are copied from `source`, 3.1 and a placeholder when it has none.
+pub fn to_openapi(apis: &[Api], source: &Yaml) -> Yaml {
+  let version = version_of(source);
+  let info = match &source["info"] {
+    Yaml::Hash(_) => source["info"].clone(),
+    _ => default_info(),
+  };
+  sort_keys(
+    &hash(vec![
+      (
+        "openapi",
+        source["openapi"]
+          .as_str()
+          .map(key)
+          .unwrap_or_else(|| key("3.1.0")),
+      ),
+      ("info", info),
+      (
+        "paths",
+        Yaml::Hash(
+          apis
+            .iter()
+            .map(|api| (key(&api.path), path_item(api, version)))
+            .collect(),
+        ),
+      ),
+    ]),
+    "",
+  )
+}
+
+fn key_text(yaml: &Yaml) -> String {
+  match yaml {
+    Yaml::String(text) => text.clone(),
+    Yaml::Integer(value) => value.to_string(),
+    Yaml::Real(value) => value.clone(),
+    Yaml::Boolean(value) => value.to_string(),
+    _ => "".to_string(),
+  }
+}
+
+// `openapi` and `info` lead the document; property order is the field order of generated
+// types, and examples, defaults and enums are data
+fn sort_keys(yaml: &Yaml, parent: &str) -> Yaml {
+  match yaml {
+    _ if ["example", "examples", "default", "enum", "const"].contains(&parent) => yaml.clone(),
+    Yaml::Hash(hash) => {
+      let mut entries = hash
+        .iter()
+        .map(|(name, value)| {
+          let text = key_text(name);
+          let value = if parent == "properties" {
+            sort_keys(value, "")
+          } else {
+            sort_keys(value, &text)
+          };
+          (name.clone(), text, value)
+        })
+        .collect::<Vec<_>>();
+      if parent != "properties" {
+        let rank = |text: &str| match (parent, text) {
+          ("", "openapi") => 0,
+          ("", "info") => 1,
+          _ => 2,
+        };
+        entries.sort_by(|a, b| (rank(&a.1), &a.1).cmp(&(rank(&b.1), &b.1)));
+      }
+      Yaml::Hash(
+        entries
+          .into_iter()
+          .map(|(name, _, value)| (name, value))
+          .collect(),
+      )
+    }
+    Yaml::Array(items) => Yaml::Array(items.iter().map(|item| sort_keys(item, "")).collect()),
+    _ => yaml.clone(),
+  }
+}
+
+fn fill_parameters(parameters: &mut Yaml) {
+  if let Yaml::Array(parameters) = parameters {
+    for parameter in parameters.iter_mut() {
+      if let Yaml::Hash(parameter) = parameter {
+        if parameter.get(&key("in")) == Some(&key("path")) {
+          parameter.insert(key("required"), Yaml::Boolean(true));
+        }
+      }
+    }
+  }
+}
+
+fn fill_defaults(document: &mut Yaml) {
+  if let Yaml::Hash(root) = document {
+    if !matches!(root.get(&key("info")), Some(Yaml::Hash(_))) {
+      root.insert(key("info"), default_info());
+    }
+    let paths = root.get_mut(&key("paths")).and_then(|paths| match paths {
+      Yaml::Hash(paths) => Some(paths),
+      _ => None,
+    });
+    for (_, item) in paths.into_iter().flatten() {
+      let item = match item {
+        Yaml::Hash(item) => item,
+        _ => continue,
+      };
+      for (name, value) in item.iter_mut() {
+        let name = key_text(name);
+        if name == "parameters" {
+          fill_parameters(value);
+        }
+        if !OPERATIONS.contains(&&name[..]) {
+          continue;
+        }
+        if let Yaml::Hash(operation) = value {
+          if let Some(parameters) = operation.get_mut(&key("parameters")) {
+            fill_parameters(parameters);
+          }
+          if let Some(Yaml::Hash(responses)) = operation.get_mut(&key("responses")) {
+            for (status, response) in responses.iter_mut() {
+              if let Yaml::Hash(response) = response {
+                if !response.contains_key(&key("description")) {
+                  response.insert(key("description"), response_description(&key_text(status)));
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Tidies a bundled document without going through the model, so nothing is lost: keys are
+/// sorted and path parameters are marked required, `info` and response descriptions are filled
+/// in where missing. Swagger 2.0 documents are converted to OpenAPI 3.0 first.
+pub fn normalize(document: &Yaml) -> Yaml {
+  let mut document = crate::swagger2::as_openapi3(document);
+  fill_defaults(&mut document);
+  sort_keys(&document, "")
+}
+
+pub fn to_yaml_string(document: &Yaml) -> String {
+  let mut out = String::new();
+  YamlEmitter::new(&mut out)
+    .dump(document)
+    .expect("can not emit YAML");
+  out + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::apis::from_yaml;
+  use yaml_rust::YamlLoader;
+
+  // the order `to_openapi` writes paths, methods and statuses in
+  fn sorted(apis: Vec<Api>) -> Vec<Api> {
+    let mut apis = apis
+      .into_iter()
+      .map(|mut api| {
+        let mut methods = api
+          .method_map
+          .into_iter()
+          .map(|(name, mut method)| {
+            let mut responses = method.response_map.into_iter().collect::<Vec<_>>();
+            responses.sort_by(|a, b| a.0.cmp(&b.0));
+            method.response_map = responses.into_iter().collect();
+            (name, method)
+          })
+          .collect::<Vec<_>>();
+        methods.sort_by(|a, b| a.0.cmp(&b.0));
+        api.method_map = methods.into_iter().collect();
+        api
+      })
+      .collect::<Vec<_>>();
+    apis.sort_by(|a, b| a.path.cmp(&b.path));
+    apis
+  }
+
+  fn round_trip(yaml: &str) {
+    let source = &YamlLoader::load_from_str(yaml).unwrap()[0];
+    let apis = sorted(from_yaml(source));
+    let document = to_openapi(&apis, source);
+    assert_eq!(source["openapi"], document["openapi"]);
+    assert_eq!(apis, from_yaml(&document));
+    let emitted = YamlLoader::load_from_str(&to_yaml_string(&document)).unwrap();
+    assert_eq!(apis, from_yaml(&emitted[0]));
+    assert_eq!(
+      apis,
+      from_yaml(
+        &crate::loader::load_from_str(
+          &crate::loader::to_json(&document),
+          crate::loader::Format::Json
+        )
+        .unwrap()
+      )
+    );
+  }
+
+  #[test]
+  fn it_normalize() {
+    let source = &YamlLoader::load_from_str(
+      "
+      paths:
+        /users/{userId}:
+          parameters:
+            - name: userId
+              in: path
+              schema:
+                type: string
+          delete:
+            summary: ユーザ削除
+            operationId: delete-users-userId
+            responses:
+              '204': {}
+          get:
+            summary: ユーザ詳細GET
+            operationId: get-users-userId
+            tags: [users]
+            parameters:
+              - name: verbose
+                in: query
+                schema:
+                  type: boolean
+              - name: X-Request-Id
+                in: header
+                schema:
+                  type: string
+            responses:
+              '200':
+                description: OK
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      required: [id]
+                      properties:
+                        name:
+                          type: string
+                          example: {b: 1, a: 2}
+                        id:
+                          type: string
+      servers:
+        - url: https://api.example.com
+      tags:
+        - name: users
+      security:
+        - bearer: []
+      components:
+        securitySchemes:
+          bearer:
+            type: http
+            scheme: bearer
+        schemas:
+          User:
+            type: object
+            required: [id]
+            properties:
+              name:
+                type: string
+              id:
+                type: string
+      openapi: 3.0.3
+      ",
+    )
+    .unwrap()[0];
+    let document = normalize(source);
+    let keys = |yaml: &Yaml| {
+      yaml
+        .as_hash()
+        .unwrap()
+        .keys()
+        .map(|key| key.as_str().unwrap().to_string())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      vec![
+        "openapi",
+        "info",
+        "components",
+        "paths",
+        "security",
+        "servers",
+        "tags"
+      ],
+      keys(&document)
+    );
+    assert_eq!(Some("3.0.3"), document["openapi"].as_str());
+    let components = &document["components"];
+    assert_eq!(vec!["schemas", "securitySchemes"], keys(components));
+    assert_eq!(
+      Some("bearer"),
+      components["securitySchemes"]["bearer"]["scheme"].as_str()
+    );
+    assert_eq!(
+      Some("id"),
+      components["schemas"]["User"]["required"][0].as_str()
+    );
+    assert_eq!(
+      vec!["name", "id"],
+      keys(&document["components"]["schemas"]["User"]["properties"])
+    );
+    let item = &document["paths"]["/users/{userId}"];
+    assert_eq!(vec!["delete", "get", "parameters"], keys(item));
+    assert_eq!(Some(true), item["parameters"][0]["required"].as_bool());
+    let parameters = &item["get"]["parameters"];
+    assert_eq!(Some("verbose"), parameters[0]["name"].as_str());
+    assert_eq!(Some("query"), parameters[0]["in"].as_str());
+    assert_eq!(Some("X-Request-Id"), parameters[1]["name"].as_str());
+    assert_eq!(Some("header"), parameters[1]["in"].as_str());
+    assert_eq!(Some("users"), item["get"]["tags"][0].as_str());
+    assert_eq!(
+      Some("204 response"),
+      item["delete"]["responses"]["204"]["description"].as_str()
+    );
+    let schema = &item["get"]["responses"]["200"]["content"]["application/json"]["schema"];
+    assert_eq!(
+      Some("OK"),
+      item["get"]["responses"]["200"]["description"].as_str()
+    );
+    assert_eq!(Some("id"), schema["required"][0].as_str());
+    assert_eq!(
+      vec!["b", "a"],
+      keys(&schema["properties"]["name"]["example"])
+    );
+    assert_eq!(source["security"], document["security"]);
+    assert_eq!(source["servers"], document["servers"]);
+    assert_eq!(from_yaml(source), from_yaml(&document));
+  }
+
+  #[test]
+  fn it_round_trip_openapi_30() {
+    round_trip(
+      "
+      openapi: 3.0.0
+      paths:
+        /orgs/{orgId}/users/{userId}:
+          parameters:
+            - name: orgId
+              in: path
+              schema:
+                type: string
+            - name: userId
+              in: path
+              schema:
+                type: integer
+          put:
+            summary: ユーザ詳細PUT
+            operationId: put-users-userId
+            description: 'updates: name and rank'
+            deprecated: true
+            externalDocs:
+              url: https://example.com/users
+            requestBody:
+              content:
+                multipart/form-data:
+                  schema:
+                    type: object
+                    properties:
+                      name:
+                        type: string
+                        nullable: true
+                        minLength: 1
+                        maxLength: 20
+                        pattern: '^[a-z]+$'
+                        example: 'null'
+                      rank:
+                        type: string
+                        enum: [A, B, 'true']
+                      grade:
+                        type: string
+                        nullable: true
+                        enum: [S, null]
+                      version:
+                        type: integer
+                        enum: [2]
+                      score:
+                        type: number
+                        minimum: 0
+                        maximum: 1.5
+                      tags:
+                        type: array
+                        items:
+                          type: string
+                        uniqueItems: true
+                        maxItems: 3
+                      avatar:
+                        type: string
+                        format: binary
+                      birthday:
+                        type: string
+                        format: date
+                        title: Birthday
+            responses:
+              200:
+                description: OK
+                content:
+                  text/csv:
+                    schema:
+                      type: string
+              '404':
+                description: Not Found
+                content:
+                  application/problem+json:
+                    schema:
+                      type: object
+                      properties:
+                        detail:
+                          type: [string, integer]
+          get:
+            summary: ユーザ詳細GET
+            operationId: get-users-userId
+            responses:
+              '204':
+                description: No Content
+      ",
+    );
+  }
+
+  #[test]
+  fn it_round_trip_openapi_31() {
+    round_trip(
+      "
+      openapi: 3.1.0
+      paths:
+        /points:
+          post:
+            summary: 座標登録
+            operationId: post-points
+            requestBody:
+              content:
+                application/json:
+                  schema:
+                    type: object
+                    required: [point]
+                    properties:
+                      point:
+                        type: array
+                        prefixItems:
+                          - type: number
+                          - type: number
+                      kind:
+                        const: point
+                      version:
+                        const: 2
+                      level:
+                        anyOf:
+                          - const: 1.5
+                          - type: 'null'
+                      label:
+                        type: [string, boolean, 'null']
+                      meta:
+                        type: [object, 'null']
+                        properties:
+                          active:
+                            type: boolean
+                      target:
+                        anyOf:
+                          - type: object
+                            properties:
+                              id:
+                                type: integer
+                          - type: array
+                            items:
+                              type: string
+                          - type: string
+                            format: date
+                          - type: 'null'
+            responses:
+              '201':
+                description: Created
+                content:
+                  application/json:
+                    schema:
+                      type: array
+                      items:
+                        type: integer
+      ",
+    );
+  }
+}
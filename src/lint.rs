@@ -0,0 +1,356 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use yaml_rust::Yaml;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Severity {
+  Warning,
+  Error,
+}
+
+pub const RULES: [(&str, Severity); 6] = [
+  ("missing-operation-id", Severity::Error),
+  ("duplicate-operation-id", Severity::Error),
+  ("empty-summary", Severity::Warning),
+  ("undeclared-path-parameter", Severity::Error),
+  ("unused-component", Severity::Warning),
+  ("invalid-property-name", Severity::Warning),
+];
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Violation {
+  pub rule: String,
+  pub severity: Severity,
+  pub location: String,
+  pub message: String,
+}
+
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}[{}] {}: {}",
+      match self.severity {
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+      },
+      self.rule,
+      self.location,
+      self.message
+    )
+  }
+}
+
+/// Rule severities, `None` when the rule is turned off.
+#[derive(PartialEq, Clone, Debug)]
+pub struct LintConfig {
+  pub severities: HashMap<String, Option<Severity>>,
+}
+
+impl Default for LintConfig {
+  fn default() -> LintConfig {
+    LintConfig {
+      severities: RULES
+        .iter()
+        .map(|(rule, severity)| (rule.to_string(), Some(*severity)))
+        .collect(),
+    }
+  }
+}
+
+impl LintConfig {
+  /// Reads a rules file such as
+  ///
+  /// ```yaml
+  /// rules:
+  ///   empty-summary: error
+  ///   unused-component: off
+  /// ```
+  pub fn from_yaml(yaml: &Yaml) -> Result<LintConfig, String> {
+    let mut config = LintConfig::default();
+    for (rule, severity) in yaml["rules"].as_hash().into_iter().flatten() {
+      let rule = rule.as_str().ok_or("rule name must be string")?;
+      if !config.severities.contains_key(rule) {
+        return Err(format!("unknown rule: {}", rule));
+      }
+      let severity = match severity {
+        Yaml::String(severity) if severity == "error" => Some(Severity::Error),
+        Yaml::String(severity) if severity == "warning" => Some(Severity::Warning),
+        Yaml::String(severity) if severity == "off" => None,
+        Yaml::Boolean(false) => None,
+        _ => return Err(format!("invalid severity for {}: {:?}", rule, severity)),
+      };
+      config.severities.insert(rule.to_string(), severity);
+    }
+    Ok(config)
+  }
+}
+
+fn escape(token: &str) -> String {
+  token.replace("~", "~0").replace("/", "~1")
+}
+
+const METHODS: [&str; 8] = [
+  "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Checks operations on the resolved `document`, and components and property names on the
+/// document as written, so locations point at the spec file itself.
+pub fn lint(document: &Yaml, unresolved_document: &Yaml, config: &LintConfig) -> Vec<Violation> {
+  let mut violations = vec![];
+  let mut report = |rule: &str, location: String, message: String| {
+    if let Some(Some(severity)) = config.severities.get(rule) {
+      violations.push(Violation {
+        rule: rule.to_string(),
+        severity: *severity,
+        location,
+        message,
+      });
+    }
+  };
+
+  let mut operation_ids: Vec<(String, String)> = vec![];
+  let variable_reg = Regex::new(r"\{([^}]*)\}").unwrap();
+  for (path, path_item) in document["paths"].as_hash().into_iter().flatten() {
+    let path = path.as_str().unwrap_or("");
+    let path_location = format!("#/paths/{}", escape(path));
+    let path_parameters = declared_path_parameters(&path_item["parameters"]);
+
+    for method in METHODS.iter() {
+      let operation = &path_item[*method];
+      if operation.is_badvalue() {
+        continue;
+      }
+      let location = format!("{}/{}", path_location, method);
+
+      match operation["operationId"].as_str() {
+        Some(operation_id) => match operation_ids.iter().find(|(id, _)| id == operation_id) {
+          Some((_, other)) => report(
+            "duplicate-operation-id",
+            location.clone(),
+            format!(
+              "operationId '{}' is also used by {}, generated files would overwrite each other",
+              operation_id, other
+            ),
+          ),
+          None => operation_ids.push((operation_id.to_string(), location.clone())),
+        },
+        None => report(
+          "missing-operation-id",
+          location.clone(),
+          "operation has no operationId".to_string(),
+        ),
+      }
+
+      if operation["summary"]
+        .as_str()
+        .map(|summary| summary.trim().is_empty())
+        .unwrap_or(true)
+      {
+        report(
+          "empty-summary",
+          location.clone(),
+          "operation has no summary".to_string(),
+        );
+      }
+
+      let mut declared = path_parameters.clone();
+      declared.extend(declared_path_parameters(&operation["parameters"]));
+      for captures in variable_reg.captures_iter(path) {
+        if !declared.contains(&captures[1].to_string()) {
+          report(
+            "undeclared-path-parameter",
+            location.clone(),
+            format!(
+              "path parameter '{}' is not declared in parameters",
+              &captures[1]
+            ),
+          );
+        }
+      }
+    }
+  }
+
+  let references = collect_references(unresolved_document);
+  for (section, components) in unresolved_document["components"]
+    .as_hash()
+    .into_iter()
+    .flatten()
+  {
+    let section = section.as_str().unwrap_or("");
+    for name in components
+      .as_hash()
+      .into_iter()
+      .flatten()
+      .map(|(name, _)| name)
+    {
+      let name = name.as_str().unwrap_or("");
+      let pointer = format!("#/components/{}/{}", section, escape(name));
+      if !references
+        .iter()
+        .any(|reference| reference == &pointer || reference.starts_with(&format!("{}/", pointer)))
+      {
+        report(
+          "unused-component",
+          pointer,
+          format!("component '{}' is never referenced", name),
+        );
+      }
+    }
+  }
+
+  let identifier_reg = Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap();
+  let mut property_names = vec![];
+  collect_property_names(unresolved_document, "#".to_string(), &mut property_names);
+  for (location, name) in property_names {
+    if !identifier_reg.is_match(&name) {
+      report(
+        "invalid-property-name",
+        location,
+        format!("property '{}' is not a valid identifier", name),
+      );
+    }
+  }
+
+  violations
+}
+
+fn declared_path_parameters(parameters: &Yaml) -> Vec<String> {
+  parameters
+    .as_vec()
+    .into_iter()
+    .flatten()
+    .filter(|parameter| parameter["in"].as_str() == Some("path"))
+    .filter_map(|parameter| parameter["name"].as_str().map(|name| name.to_string()))
+    .collect()
+}
+
+fn collect_references(yaml: &Yaml) -> Vec<String> {
+  match yaml {
+    Yaml::Hash(hash) => hash
+      .iter()
+      .flat_map(|(key, value)| match (key.as_str(), value) {
+        (Some("$ref"), Yaml::String(reference)) => match reference.find('#') {
+          Some(0) => vec![reference.clone()],
+          _ => vec![],
+        },
+        _ => collect_references(value),
+      })
+      .collect(),
+    Yaml::Array(items) => items.iter().flat_map(collect_references).collect(),
+    _ => vec![],
+  }
+}
+
+fn collect_property_names(yaml: &Yaml, location: String, names: &mut Vec<(String, String)>) {
+  match yaml {
+    Yaml::Hash(hash) => {
+      for (key, value) in hash {
+        let key = key.as_str().unwrap_or("");
+        let child_location = format!("{}/{}", location, escape(key));
+        if key == "properties" && !hash.contains_key(&Yaml::String("$ref".to_string())) {
+          for (name, schema) in value.as_hash().into_iter().flatten() {
+            let name = name.as_str().unwrap_or("");
+            let property_location = format!("{}/{}", child_location, escape(name));
+            names.push((property_location.clone(), name.to_string()));
+            collect_property_names(schema, property_location, names);
+          }
+        } else {
+          collect_property_names(value, child_location, names);
+        }
+      }
+    }
+    Yaml::Array(items) => {
+      for (index, item) in items.iter().enumerate() {
+        collect_property_names(item, format!("{}/{}", location, index), names);
+      }
+    }
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use yaml_rust::YamlLoader;
+
+  #[test]
+  fn it_lint() {
+    let yaml = "
+      openapi: 3.0.0
+      paths:
+        '/users/{userId}':
+          get:
+            summary: ' '
+            operationId: get-users
+            responses:
+              '200':
+                content:
+                  application/json:
+                    schema:
+                      $ref: '#/components/schemas/User'
+        /users:
+          get:
+            summary: ユーザ取得
+            operationId: get-users
+          post:
+            summary: ユーザ作成
+      components:
+        schemas:
+          User:
+            type: object
+            properties:
+              user-name:
+                type: string
+          Unused:
+            type: string
+      ";
+    let doc = &YamlLoader::load_from_str(yaml).unwrap()[0];
+    let location = |path: &str| format!("#/paths/{}", path);
+
+    assert_eq!(
+      vec![
+        (
+          "empty-summary".to_string(),
+          location("~1users~1{userId}/get")
+        ),
+        (
+          "undeclared-path-parameter".to_string(),
+          location("~1users~1{userId}/get")
+        ),
+        (
+          "duplicate-operation-id".to_string(),
+          location("~1users/get")
+        ),
+        ("missing-operation-id".to_string(), location("~1users/post")),
+        (
+          "unused-component".to_string(),
+          "#/components/schemas/Unused".to_string()
+        ),
+        (
+          "invalid-property-name".to_string(),
+          "#/components/schemas/User/properties/user-name".to_string()
+        ),
+      ],
+      lint(doc, doc, &LintConfig::default())
+        .into_iter()
+        .map(|violation| (violation.rule, violation.location))
+        .collect::<Vec<_>>()
+    );
+
+    let rules = &YamlLoader::load_from_str(
+      "rules:\n  empty-summary: error\n  unused-component: off\n  invalid-property-name: off\n  undeclared-path-parameter: off\n  duplicate-operation-id: off\n  missing-operation-id: off",
+    )
+    .unwrap()[0];
+    assert_eq!(
+      vec![Violation {
+        rule: "empty-summary".to_string(),
+        severity: Severity::Error,
+        location: location("~1users~1{userId}/get"),
+        message: "operation has no summary".to_string(),
+      }],
+      lint(doc, doc, &LintConfig::from_yaml(rules).unwrap())
+    );
+  }
+}
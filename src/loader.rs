@@ -0,0 +1,533 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::rc::Rc;
+use yaml_rust::yaml::Hash;
+use yaml_rust::{Yaml, YamlLoader};
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Format {
+  Yaml,
+  Json,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct LoadError {
+  pub message: String,
+  pub line: usize,
+  pub col: usize,
+}
+
+impl fmt::Display for LoadError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{} at line {} column {}",
+      self.message, self.line, self.col
+    )
+  }
+}
+
+pub fn detect_format(path: &str, source: &str) -> Format {
+  if path.to_lowercase().ends_with(".json") {
+    Format::Json
+  } else if path.to_lowercase().ends_with(".yaml") || path.to_lowercase().ends_with(".yml") {
+    Format::Yaml
+  } else {
+    match source.trim_start().chars().next() {
+      Some('{') | Some('[') => Format::Json,
+      _ => Format::Yaml,
+    }
+  }
+}
+
+pub fn load_from_str(source: &str, format: Format) -> Result<Yaml, LoadError> {
+  match format {
+    Format::Yaml => YamlLoader::load_from_str(source)
+      .map_err(|err| {
+        let (line, col) = (err.marker().line(), err.marker().col() + 1);
+        LoadError {
+          message: err
+            .to_string()
+            .trim_end_matches(&format!(" at line {} column {}", line, col))
+            .to_string(),
+          line,
+          col,
+        }
+      })
+      .map(|docs| docs.into_iter().next().unwrap_or(Yaml::Null)),
+    Format::Json => serde_json::from_str(source).map(from_json).map_err(|err| {
+      let (line, col) = (err.line(), err.column());
+      LoadError {
+        message: err
+          .to_string()
+          .trim_end_matches(&format!(" at line {} column {}", line, col))
+          .to_string(),
+        line,
+        col,
+      }
+    }),
+  }
+}
+
+pub fn load_file(path: &str) -> Result<Yaml, String> {
+  load_file_with_sources(path).0
+}
+
+/// Like `load_file`, also returning every file it read or tried to read through `$ref`s, so
+/// they can be watched even when loading failed.
+pub fn load_file_with_sources(path: &str) -> (Result<Yaml, String>, Vec<PathBuf>) {
+  let file = PathBuf::from(path);
+  let mut resolver = RefResolver::new(&file);
+  let document = resolver
+    .read_document(&file)
+    .and_then(|root| resolver.resolve(&root, &file, &root));
+  (document, resolver.sources)
+}
+
+/// Reads the document without inlining `$ref`s, for checks on the spec as written.
+pub fn load_unresolved_file(path: &str) -> Result<Yaml, String> {
+  let file = PathBuf::from(path);
+  RefResolver::new(&file)
+    .read_document(&file)
+    .map(|document| (*document).clone())
+}
+
+struct RefResolver {
+  entry: PathBuf,
+  documents: HashMap<PathBuf, Rc<Yaml>>,
+  // resolved targets by `file#fragment`, shared schemas are walked once
+  resolved: HashMap<String, Yaml>,
+  stack: Vec<String>,
+  // `$defs` of the enclosing schemas, innermost last
+  defs: Vec<Yaml>,
+  sources: Vec<PathBuf>,
+}
+
+impl RefResolver {
+  fn new(entry: &Path) -> RefResolver {
+    RefResolver {
+      entry: entry.to_path_buf(),
+      documents: HashMap::new(),
+      resolved: HashMap::new(),
+      stack: vec![],
+      defs: vec![],
+      sources: vec![],
+    }
+  }
+
+  fn read_document(&mut self, file: &Path) -> Result<Rc<Yaml>, String> {
+    if let Some(document) = self.documents.get(file) {
+      return Ok(Rc::clone(document));
+    }
+    if !self.sources.iter().any(|source| source == file) {
+      self.sources.push(file.to_path_buf());
+    }
+    let source = if file == Path::new("-") {
+      let mut source = String::new();
+      io::stdin()
+        .read_to_string(&mut source)
+        .map_err(|err| format!("-: {}", err))?;
+      source
+    } else {
+      fs::read_to_string(file).map_err(|err| format!("{}: {}", file.display(), err))?
+    };
+    let format = detect_format(&file.to_string_lossy(), &source);
+    let document = Rc::new(
+      load_from_str(&source, format).map_err(|err| format!("{}: {}", file.display(), err))?,
+    );
+    self
+      .documents
+      .insert(file.to_path_buf(), Rc::clone(&document));
+    Ok(document)
+  }
+
+  fn resolve(&mut self, value: &Yaml, file: &Path, root: &Yaml) -> Result<Yaml, String> {
+    match value {
+      Yaml::Hash(hash) => match hash.get(&Yaml::String("$ref".to_string())) {
+        Some(Yaml::String(reference)) => self.resolve_ref(reference, file, root),
+        _ => {
+          let defs = hash.get(&Yaml::String("$defs".to_string())).cloned();
+          let has_defs = defs.is_some();
+          self.defs.extend(defs);
+          let resolved = hash
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), self.resolve(value, file, root)?)))
+            .collect::<Result<Hash, String>>();
+          if has_defs {
+            self.defs.pop();
+          }
+          resolved.map(Yaml::Hash)
+        }
+      },
+      Yaml::Array(items) => items
+        .iter()
+        .map(|item| self.resolve(item, file, root))
+        .collect::<Result<Vec<_>, _>>()
+        .map(Yaml::Array),
+      value => Ok(value.clone()),
+    }
+  }
+
+  fn resolve_ref(&mut self, reference: &str, file: &Path, root: &Yaml) -> Result<Yaml, String> {
+    let (target_path, fragment) = match reference.find('#') {
+      Some(index) => (&reference[..index], &reference[index + 1..]),
+      None => (reference, ""),
+    };
+    let target_file = if target_path.is_empty() {
+      file.to_path_buf()
+    } else {
+      normalize_path(
+        &file
+          .parent()
+          .unwrap_or_else(|| Path::new(""))
+          .join(target_path),
+      )
+    };
+
+    let key = format!("{}#{}", target_file.display(), fragment);
+    if let Some(resolved) = self.resolved.get(&key) {
+      return Ok(resolved.clone());
+    }
+    // recursive schemas, e.g. tree nodes, stay a reference where they repeat
+    if self.stack.contains(&key) {
+      return Ok(self.reference(&target_file, fragment));
+    }
+
+    let external;
+    let target_root = if target_path.is_empty() {
+      root
+    } else {
+      external = self.read_document(&target_file)?;
+      &*external
+    };
+    let target = match pointer(target_root, fragment) {
+      Some(target) => target,
+      // `#/$defs/...` of an embedded schema points into the enclosing schema's `$defs`
+      None if target_path.is_empty() && fragment.starts_with("/$defs/") => {
+        let defs = self
+          .defs
+          .iter()
+          .rev()
+          .find_map(|defs| pointer(defs, &fragment["/$defs".len()..]))
+          .cloned()
+          .ok_or_else(|| format!("{}: can not resolve $ref '{}'", file.display(), reference))?;
+        self.stack.push(key);
+        let resolved = self.resolve(&defs, &target_file, target_root);
+        self.stack.pop();
+        return resolved;
+      }
+      None => {
+        return Err(format!(
+          "{}: can not resolve $ref '{}'",
+          file.display(),
+          reference
+        ))
+      }
+    };
+    self.stack.push(key.clone());
+    let resolved = self.resolve(target, &target_file, target_root);
+    self.stack.pop();
+    let resolved = resolved?;
+    self.resolved.insert(key, resolved.clone());
+    Ok(resolved)
+  }
+
+  // relative to the entry document, the only place the inlined reference can appear
+  fn reference(&self, target_file: &Path, fragment: &str) -> Yaml {
+    let path = if target_file == self.entry {
+      String::new()
+    } else {
+      target_file
+        .strip_prefix(self.entry.parent().unwrap_or_else(|| Path::new("")))
+        .unwrap_or(target_file)
+        .to_string_lossy()
+        .to_string()
+    };
+    let mut hash = Hash::new();
+    hash.insert(
+      Yaml::String("$ref".to_string()),
+      Yaml::String(format!("{}#{}", path, fragment)),
+    );
+    Yaml::Hash(hash)
+  }
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+  let mut normalized = PathBuf::new();
+  for component in path.components() {
+    match component {
+      Component::CurDir => {}
+      Component::ParentDir if normalized.file_name().is_some() => {
+        normalized.pop();
+      }
+      component => normalized.push(component.as_os_str()),
+    }
+  }
+  normalized
+}
+
+fn pointer<'a>(document: &'a Yaml, fragment: &str) -> Option<&'a Yaml> {
+  fragment
+    .split('/')
+    .filter(|token| !token.is_empty())
+    .map(|token| token.replace("~1", "/").replace("~0", "~"))
+    .try_fold(document, |current, token| match current {
+      Yaml::Hash(hash) => hash.get(&Yaml::String(token)),
+      Yaml::Array(items) => token
+        .parse::<usize>()
+        .ok()
+        .and_then(|index| items.get(index)),
+      _ => None,
+    })
+}
+
+/// Pretty prints a document as JSON with two space indentation.
+pub fn to_json(yaml: &Yaml) -> String {
+  fn write(yaml: &Yaml, indent: usize, out: &mut String) {
+    let padding = "  ".repeat(indent + 1);
+    match yaml {
+      Yaml::Hash(hash) if hash.is_empty() => out.push_str("{}"),
+      Yaml::Hash(hash) => {
+        out.push_str("{\n");
+        for (index, (key, value)) in hash.iter().enumerate() {
+          out.push_str(&padding);
+          let key = match key {
+            Yaml::String(key) => key.clone(),
+            Yaml::Integer(key) => key.to_string(),
+            Yaml::Real(key) => key.clone(),
+            Yaml::Boolean(key) => key.to_string(),
+            _ => "null".to_string(),
+          };
+          write(&Yaml::String(key), indent + 1, out);
+          out.push_str(": ");
+          write(value, indent + 1, out);
+          out.push_str(if index + 1 < hash.len() { ",\n" } else { "\n" });
+        }
+        out.push_str(&"  ".repeat(indent));
+        out.push('}');
+      }
+      Yaml::Array(items) if items.is_empty() => out.push_str("[]"),
+      Yaml::Array(items) => {
+        out.push_str("[\n");
+        for (index, item) in items.iter().enumerate() {
+          out.push_str(&padding);
+          write(item, indent + 1, out);
+          out.push_str(if index + 1 < items.len() { ",\n" } else { "\n" });
+        }
+        out.push_str(&"  ".repeat(indent));
+        out.push(']');
+      }
+      Yaml::String(string) => {
+        out.push('"');
+        for c in string.chars() {
+          match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+          }
+        }
+        out.push('"');
+      }
+      Yaml::Integer(integer) => out.push_str(&integer.to_string()),
+      Yaml::Real(real) => out.push_str(real),
+      Yaml::Boolean(boolean) => out.push_str(&boolean.to_string()),
+      _ => out.push_str("null"),
+    }
+  }
+
+  let mut out = String::new();
+  write(yaml, 0, &mut out);
+  out
+}
+
+// keys keep their order and numbers their text through serde_json's `preserve_order` and
+// `arbitrary_precision`
+fn from_json(value: serde_json::Value) -> Yaml {
+  match value {
+    serde_json::Value::Null => Yaml::Null,
+    serde_json::Value::Bool(boolean) => Yaml::Boolean(boolean),
+    serde_json::Value::Number(number) => match number.as_i64() {
+      Some(integer) => Yaml::Integer(integer),
+      None => Yaml::Real(number.to_string()),
+    },
+    serde_json::Value::String(string) => Yaml::String(string),
+    serde_json::Value::Array(items) => Yaml::Array(items.into_iter().map(from_json).collect()),
+    serde_json::Value::Object(entries) => Yaml::Hash(
+      entries
+        .into_iter()
+        .map(|(key, value)| (Yaml::String(key), from_json(value)))
+        .collect(),
+    ),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_load_json_as_yaml() {
+    let json = r#"{
+        "paths": {
+          "/users": {
+            "get": {"operationId": "get-users", "tags": [], "deprecated": false, "x": 1.5, "y": null}
+          }
+        }
+      }"#;
+    let yaml = "
+      paths:
+        /users:
+          get:
+            operationId: get-users
+            tags: []
+            deprecated: false
+            x: 1.5
+            y: null
+      ";
+
+    assert_eq!(Format::Json, detect_format("openapi", json));
+    assert_eq!(Format::Yaml, detect_format("openapi", yaml));
+    assert_eq!(
+      load_from_str(yaml, Format::Yaml),
+      load_from_str(json, Format::Json)
+    );
+  }
+
+  #[test]
+  fn it_to_json() {
+    let json = "{\n  \"name\": \"a \\\"quoted\\\" \\n name\",\n  \"tags\": [\n    1,\n    2.5,\n    true,\n    null\n  ],\n  \"empty\": {}\n}";
+    let yaml = load_from_str(json, Format::Json).unwrap();
+
+    assert_eq!(json, to_json(&yaml));
+  }
+
+  #[test]
+  fn it_load_json_error_position() {
+    let json = "{\n  \"paths\": {\n    \"/users\": 1,\n  }\n}";
+    assert_eq!(
+      Err(LoadError {
+        message: "trailing comma".to_string(),
+        line: 4,
+        col: 3
+      }),
+      load_from_str(json, Format::Json)
+    );
+    assert_eq!(
+      Err(LoadError {
+        message: "mapping values are not allowed in this context".to_string(),
+        line: 2,
+        col: 7
+      }),
+      load_from_str("paths:\n  a: b: c", Format::Yaml)
+    );
+    for (json, message) in [
+      ("[01]", "invalid number"),
+      ("[1.]", "invalid number"),
+      (
+        "[\"\\ud83d\\u0041\"]",
+        "lone leading surrogate in hex escape",
+      ),
+    ] {
+      assert_eq!(
+        Err(message.to_string()),
+        load_from_str(json, Format::Json).map_err(|err| err.message)
+      );
+    }
+    assert_eq!(
+      Ok(Yaml::Array(vec![
+        Yaml::Integer(0),
+        Yaml::Real("-0.5e+3".to_string()),
+        Yaml::String("😀".to_string())
+      ])),
+      load_from_str("[0, -0.5e+3, \"\\ud83d\\ude00\"]", Format::Json)
+    );
+  }
+
+  #[test]
+  fn it_load_file_with_external_refs() {
+    let dir = crate::TestDir::new("it-load-file-with-external-refs");
+    fs::create_dir_all(dir.join("schemas")).unwrap();
+    fs::create_dir_all(dir.join("paths")).unwrap();
+    fs::write(
+      dir.join("openapi.yaml"),
+      "paths:\n  /users:\n    $ref: './paths/users.yaml'\n",
+    )
+    .unwrap();
+    fs::write(
+      dir.join("paths/users.yaml"),
+      "get:\n  schema:\n    $ref: '../schemas/common.yaml#/components/schemas/Money'\n",
+    )
+    .unwrap();
+    fs::write(
+      dir.join("schemas/common.yaml"),
+      "components:\n  schemas:\n    Money:\n      type: object\n      properties:\n        currency:\n          $ref: '#/components/schemas/Currency'\n    Currency:\n      type: string\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+      Ok(
+        load_from_str(
+          "paths:\n  /users:\n    get:\n      schema:\n        type: object\n        properties:\n          currency:\n            type: string\n",
+          Format::Yaml
+        )
+        .unwrap()
+      ),
+      load_file(&dir.join("openapi.yaml").to_string_lossy())
+    );
+    assert_eq!(
+      vec![
+        dir.join("openapi.yaml"),
+        dir.join("paths/users.yaml"),
+        dir.join("schemas/common.yaml")
+      ],
+      load_file_with_sources(&dir.join("openapi.yaml").to_string_lossy()).1
+    );
+  }
+
+  #[test]
+  fn it_load_file_with_defs() {
+    let dir = crate::TestDir::new("it-load-file-with-defs");
+    fs::write(
+      dir.join("openapi.yaml"),
+      "components:\n  schemas:\n    User:\n      $defs:\n        Name:\n          type: string\n      properties:\n        name:\n          $ref: '#/$defs/Name'\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+      load_from_str("type: string", Format::Yaml).unwrap(),
+      load_file(&dir.join("openapi.yaml").to_string_lossy()).unwrap()["components"]["schemas"]
+        ["User"]["properties"]["name"]
+    );
+  }
+
+  #[test]
+  fn it_load_file_with_recursive_refs() {
+    let dir = crate::TestDir::new("it-load-file-with-recursive-refs");
+    fs::write(
+      dir.join("a.yaml"),
+      "node:\n  $ref: '#/components/Node'\ncomponents:\n  Node:\n    children:\n      items:\n        $ref: 'b.yaml#/Tree'\n",
+    )
+    .unwrap();
+    fs::write(
+      dir.join("b.yaml"),
+      "Tree:\n  node:\n    $ref: 'a.yaml#/components/Node'\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+      load_from_str(
+        "node:\n  children:\n    items:\n      node:\n        $ref: '#/components/Node'\n",
+        Format::Yaml
+      )
+      .unwrap()["node"],
+      load_file(&dir.join("a.yaml").to_string_lossy()).unwrap()["node"]
+    );
+  }
+}
@@ -1,91 +1,563 @@
 extern crate yaml_rust;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
-use yaml_rust::YamlLoader;
+use std::io;
+use std::panic;
+use std::time::Duration;
+extern crate linked_hash_map;
 extern crate regex;
 
-fn main() -> std::io::Result<()> {
-  let args: Vec<String> = env::args().collect();
-  if !args.is_empty() {
-    let yaml = fs::read_to_string(&*args[1])?;
-    let docs = YamlLoader::load_from_str(&*yaml).unwrap();
-    let doc = &docs[0];
+const USAGE: &str = "command use: ./draco-open-api [input.yaml|-] [--config draco.toml] [--output dist] [--targets scala,typescript,...] [--python-dataclass] [--python-enum] [--java-pojo] [--example-nulls] [--watch]
+             ./draco-open-api lint input.yaml [--rules rules.yaml]
+             ./draco-open-api diff old.yaml new.yaml [--json summary.json|-]
+             ./draco-open-api mock input.yaml [--port 4010]
+             ./draco-open-api validate input.yaml --operation operationId --request|--response payload.json
+             ./draco-open-api bundle|normalize input.yaml [--output openapi.yaml|openapi.json] [--json] [--model]
+             ./draco-open-api templates [dir]";
+
+fn lint_command(args: &[String]) -> io::Result<()> {
+  let input = args
+    .iter()
+    .find(|arg| !arg.starts_with("--") && Some(*arg) != rules_path(args).as_ref())
+    .expect("command use: ./draco-open-api lint input.yaml [--rules rules.yaml]");
+  let config = match rules_path(args) {
+    Some(path) => lint::LintConfig::from_yaml(
+      &loader::load_file(&path).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+    )
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path, err)))?,
+    None => lint::LintConfig::default(),
+  };
+  let document =
+    loader::load_file(input).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+  let unresolved_document = loader::load_unresolved_file(input)
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+  let violations = lint::lint(&document, &unresolved_document, &config);
+  for violation in violations.iter() {
+    println!("{}", violation);
+  }
+  let errors = violations
+    .iter()
+    .filter(|violation| violation.severity == lint::Severity::Error)
+    .count();
+  println!("{} errors, {} warnings", errors, violations.len() - errors);
+  if errors > 0 {
+    std::process::exit(1);
+  }
+  Ok(())
+}
+
+fn diff_command(args: &[String]) -> io::Result<()> {
+  let json_path = args
+    .iter()
+    .position(|arg| arg == "--json")
+    .and_then(|index| args.get(index + 1));
+  let inputs = args
+    .iter()
+    .filter(|arg| *arg != "--json" && Some(*arg) != json_path)
+    .collect::<Vec<_>>();
+  if inputs.len() != 2 {
+    panic!("command use: ./draco-open-api diff old.yaml new.yaml [--json summary.json|-]")
+  }
+  let load = |path: &str| {
+    loader::load_file(path)
+      .map(|document| apis::from_document(&document))
+      .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+  };
+
+  let changes = diff::diff(&load(inputs[0])?, &load(inputs[1])?);
+  print!("{}", diff::to_report(&changes));
+  match json_path.map(|path| &path[..]) {
+    Some("-") => println!("{}", diff::to_json(&changes)),
+    Some(path) => fs::write(path, diff::to_json(&changes) + "\n")?,
+    None => {}
+  }
+  if changes.iter().any(|change| change.breaking) {
+    std::process::exit(1);
+  }
+  Ok(())
+}
+
+fn mock_command(args: &[String]) -> io::Result<()> {
+  let port_arg = args
+    .iter()
+    .position(|arg| arg == "--port")
+    .and_then(|index| args.get(index + 1));
+  let input = args
+    .iter()
+    .find(|arg| *arg != "--port" && Some(*arg) != port_arg)
+    .expect("command use: ./draco-open-api mock input.yaml [--port 4010]");
+  let port = port_arg
+    .map(|port| port.parse().expect("port must be number"))
+    .unwrap_or(4010);
+  let document =
+    loader::load_file(input).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
 
-    let remove = fs::remove_dir_all("dist");
+  mock::serve(&document, port)
+}
 
-    match remove {
-      Ok(r) => r,
-      Err(_) => println!("dist create."),
+// bundle inlines every $ref as written, normalize also sorts keys and fills in defaults, and
+// `--model` writes only what the generators read
+fn bundle_command(args: &[String], normalize: bool) -> io::Result<()> {
+  let output = args
+    .iter()
+    .position(|arg| arg == "--output")
+    .and_then(|index| args.get(index + 1));
+  let input = args
+    .iter()
+    .find(|arg| !arg.starts_with("--") && Some(*arg) != output)
+    .expect("command use: ./draco-open-api bundle|normalize input.yaml [--output openapi.yaml|openapi.json] [--json] [--model]");
+  let document =
+    loader::load_file(input).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+  let document = if args.iter().any(|arg| arg == "--model") {
+    let source = swagger2::as_openapi3(&document);
+    emitter::to_openapi(&apis::from_yaml(&source), &source)
+  } else if normalize {
+    emitter::normalize(&document)
+  } else {
+    document
+  };
+  let json = args.iter().any(|arg| arg == "--json")
+    || output.map(|path| loader::detect_format(path, "")) == Some(loader::Format::Json);
+  let source = if json {
+    loader::to_json(&document) + "\n"
+  } else {
+    emitter::to_yaml_string(&document)
+  };
+  match output {
+    Some(path) => fs::write(path, source),
+    None => {
+      print!("{}", source);
+      Ok(())
     }
-    fs::create_dir("dist")?;
-    let apis = apis::from_yaml(doc);
-    for api in apis.clone() {
-      for method in api.method_map.values() {
-        let m = method.clone();
-        // create command
-        let command_scala_opt = apis::generate_command_scala(m.clone());
+  }
+}
 
-        for command_scala in command_scala_opt.iter() {
-          let dir = &format!("dist/{}/command", m.clone().operation_id);
-          fs::create_dir_all(dir)?;
-          let file = format!("{}/{}.scala", dir, m.clone().operation_id);
-          fs::write(file, command_scala)?;
-        }
+fn validate_command(args: &[String]) -> io::Result<()> {
+  let usage = "command use: ./draco-open-api validate input.yaml --operation operationId --request|--response payload.json";
+  let operation_id = args
+    .iter()
+    .position(|arg| arg == "--operation")
+    .and_then(|index| args.get(index + 1))
+    .expect(usage);
+  let (payload_kind, payload_path) = args
+    .iter()
+    .position(|arg| arg == "--request" || arg == "--response")
+    .and_then(|index| Some((&args[index], args.get(index + 1)?)))
+    .expect(usage);
+  let input = args
+    .iter()
+    .find(|arg| !arg.starts_with("--") && *arg != operation_id && *arg != payload_path)
+    .expect(usage);
+  let document =
+    loader::load_file(input).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+  let payload = loader::load_file(payload_path)
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+  let apis = apis::from_document(&document);
+  let method = validate::find_operation(&apis, operation_id).ok_or_else(|| {
+    io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("unknown operation: {}", operation_id),
+    )
+  })?;
+  let content = if payload_kind == "--request" {
+    method.request_body_opt.as_ref()
+  } else {
+    method.response_opt.as_ref()
+  }
+  .ok_or_else(|| {
+    io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("{} has no {} body", operation_id, &payload_kind[2..]),
+    )
+  })?;
 
-        let command_ts_opt = apis::generate_command_ts(m.clone());
+  let mismatches = validate::validate(&payload, content);
+  for mismatch in mismatches.iter() {
+    println!("{}", mismatch);
+  }
+  println!("{} mismatches", mismatches.len());
+  if !mismatches.is_empty() {
+    std::process::exit(1);
+  }
+  Ok(())
+}
 
-        for command_ts in command_ts_opt.iter() {
-          let dir = &format!("dist/{}/command", m.clone().operation_id);
-          fs::create_dir_all(dir)?;
-          let file = format!("{}/{}.ts", dir, m.clone().operation_id);
-          fs::write(file, command_ts)?;
-        }
+fn rules_path(args: &[String]) -> Option<String> {
+  args
+    .iter()
+    .position(|arg| arg == "--rules")
+    .and_then(|index| args.get(index + 1))
+    .cloned()
+}
 
-        // create view model
-        let view_model_scala_opt = apis::generate_view_model_scala(m.clone());
+fn main() -> io::Result<()> {
+  let args: Vec<String> = env::args().collect();
+  if args.len() > 1 && args[1] == "lint" {
+    lint_command(&args[2..])
+  } else if args.len() > 1 && args[1] == "diff" {
+    diff_command(&args[2..])
+  } else if args.len() > 1 && args[1] == "mock" {
+    mock_command(&args[2..])
+  } else if args.len() > 1 && args[1] == "validate" {
+    validate_command(&args[2..])
+  } else if args.len() > 1 && (args[1] == "bundle" || args[1] == "normalize") {
+    bundle_command(&args[2..], args[1] == "normalize")
+  } else if args.len() > 1 && args[1] == "templates" {
+    let dir = args.get(2).map(|dir| dir.as_str()).unwrap_or("templates");
+    config::write_default_templates(std::path::Path::new(dir))?;
+    println!("default templates written to {}", dir);
+    Ok(())
+  } else {
+    let config = config::Config::load(&args[1..])
+      .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let input = config.input.clone().unwrap_or_else(|| panic!("{}", USAGE));
+    if args.iter().any(|arg| arg == "--watch") {
+      return watch_command(&config, &input);
+    }
+    let doc =
+      &loader::load_file(&input).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    write_generated_files(&config, doc, true)?;
+    Ok(())
+  }
+}
 
-        for view_model_scala in view_model_scala_opt.iter() {
-          let dir = &format!("dist/{}/viewmodel", m.clone().operation_id);
-          fs::create_dir_all(dir)?;
-          let file = format!("{}/{}.scala", dir, m.clone().operation_id);
-          fs::write(file, view_model_scala)?;
+// regenerates whenever the spec or a file it references changes, until interrupted
+fn watch_command(config: &config::Config, input: &str) -> io::Result<()> {
+  if input == "-" {
+    panic!("--watch needs an input file");
+  }
+  loop {
+    let (doc, sources) = loader::load_file_with_sources(input);
+    match doc {
+      Ok(doc) => {
+        // a spec the model does not support panics, which should not end the watch
+        let generated = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+          write_generated_files(config, &doc, false)
+        }));
+        match generated {
+          Ok(Ok(written)) => println!("{} files written", written.len()),
+          Ok(Err(err)) => eprintln!("{}", err),
+          Err(_) => eprintln!("generation failed, {} is stale", config.output),
         }
+      }
+      Err(err) => eprintln!("{}", err),
+    }
+    println!("watching {} files", sources.len());
+    watch::wait_for_change(
+      &sources,
+      Duration::from_millis(300),
+      Duration::from_millis(200),
+    );
+  }
+}
+
+// a single run starts from an empty output directory, watch runs only touch what changed
+fn write_generated_files(
+  config: &config::Config,
+  doc: &yaml_rust::Yaml,
+  clean: bool,
+) -> io::Result<Vec<String>> {
+  let output = &config.output;
+  if !std::path::Path::new(output).exists() {
+    println!("{} create.", output);
+  } else if clean {
+    fs::remove_dir_all(output)?;
+  }
+  fs::create_dir_all(output)?;
+  watch::write_output(std::path::Path::new(output), &generate_files(config, doc))
+}
+
+/// Every generated file by path, in memory so unchanged files can be left untouched.
+fn generate_files(config: &config::Config, doc: &yaml_rust::Yaml) -> BTreeMap<String, String> {
+  let output = &config.output;
+  let mut files = BTreeMap::new();
+  let python_style = config.python_style;
+  let python_enums = config.python_enums;
+  let java_style = config.java_style;
+  let sample_options = config.examples;
+  // examples are looked up in the same layout the model was read from
+  let doc = &swagger2::as_openapi3(doc);
+  let apis = apis::from_yaml(doc);
+  let error_types = apis::error_types(apis.clone());
+  for api in apis.clone() {
+    for (method_type, method) in api.method_map.iter() {
+      let m = method.clone();
+      // create command
+      let command_scala_opt =
+        apis::generate_command_scala(m.clone(), &config.scala).filter(|_| config.enabled("scala"));
+
+      for command_scala in command_scala_opt.iter() {
+        let dir = &format!("{}/{}/command", output, m.clone().operation_id);
+        let file = format!("{}/{}.scala", dir, m.clone().operation_id);
+        files.insert(file, command_scala.clone());
+      }
+
+      let command_ts_opt = apis::generate_command_ts(m.clone(), &config.typescript)
+        .filter(|_| config.enabled("typescript"));
+
+      for command_ts in command_ts_opt.iter() {
+        let dir = &format!("{}/{}/command", output, m.clone().operation_id);
+        let file = format!("{}/{}.ts", dir, m.clone().operation_id);
+        files.insert(file, command_ts.clone());
+      }
+
+      let command_rust_opt =
+        apis::generate_command_rust(m.clone(), &config.rust).filter(|_| config.enabled("rust"));
+
+      for command_rust in command_rust_opt.iter() {
+        let dir = &format!("{}/{}/command", output, m.clone().operation_id);
+        let file = format!("{}/{}.rs", dir, m.clone().operation_id);
+        files.insert(file, command_rust.clone());
+      }
+
+      let command_python_opt =
+        apis::generate_command_python(m.clone(), python_style, python_enums, &config.python)
+          .filter(|_| config.enabled("python"));
+
+      for command_python in command_python_opt.iter() {
+        let dir = &format!("{}/{}/command", output, m.clone().operation_id);
+        let file = format!("{}/{}.py", dir, apis::python_module_name(m.clone()));
+        files.insert(file, command_python.clone());
+      }
+
+      let command_go_opt =
+        apis::generate_command_go(m.clone(), &config.go).filter(|_| config.enabled("go"));
+
+      for command_go in command_go_opt.iter() {
+        let dir = &format!("{}/{}/command", output, m.clone().operation_id);
+        let file = format!("{}/{}.go", dir, m.clone().operation_id);
+        files.insert(file, command_go.clone());
+      }
+
+      let command_java_opt = apis::generate_command_java(m.clone(), java_style, &config.java)
+        .filter(|_| config.enabled("java"));
+
+      for command_java in command_java_opt.iter() {
+        let dir = &format!("{}/{}/command", output, m.clone().operation_id);
+        let file = format!("{}/Command.java", dir);
+        files.insert(file, command_java.clone());
+      }
+
+      // create view model
+      let view_model_scala_opt = apis::generate_view_model_scala(m.clone(), &config.scala)
+        .filter(|_| config.enabled("scala"));
 
-        let view_model_ts_opt = apis::generate_view_model_ts(m.clone());
+      for view_model_scala in view_model_scala_opt.iter() {
+        let dir = &format!("{}/{}/viewmodel", output, m.clone().operation_id);
+        let file = format!("{}/{}.scala", dir, m.clone().operation_id);
+        files.insert(file, view_model_scala.clone());
+      }
+
+      let view_model_ts_opt = apis::generate_view_model_ts(m.clone(), &config.typescript)
+        .filter(|_| config.enabled("typescript"));
+
+      for view_model_ts in view_model_ts_opt.iter() {
+        let dir = &format!("{}/{}/viewmodel", output, m.clone().operation_id);
+        let file = format!("{}/{}.ts", dir, m.clone().operation_id);
+        files.insert(file, view_model_ts.clone());
+      }
+
+      let view_model_rust_opt =
+        apis::generate_view_model_rust(m.clone(), &config.rust).filter(|_| config.enabled("rust"));
+
+      for view_model_rust in view_model_rust_opt.iter() {
+        let dir = &format!("{}/{}/viewmodel", output, m.clone().operation_id);
+        let file = format!("{}/{}.rs", dir, m.clone().operation_id);
+        files.insert(file, view_model_rust.clone());
+      }
+
+      let view_model_python_opt =
+        apis::generate_view_model_python(m.clone(), python_style, python_enums, &config.python)
+          .filter(|_| config.enabled("python"));
+
+      for view_model_python in view_model_python_opt.iter() {
+        let dir = &format!("{}/{}/viewmodel", output, m.clone().operation_id);
+        let file = format!("{}/{}.py", dir, apis::python_module_name(m.clone()));
+        files.insert(file, view_model_python.clone());
+      }
+
+      let view_model_go_opt =
+        apis::generate_view_model_go(m.clone(), &config.go).filter(|_| config.enabled("go"));
+
+      for view_model_go in view_model_go_opt.iter() {
+        let dir = &format!("{}/{}/viewmodel", output, m.clone().operation_id);
+        let file = format!("{}/{}.go", dir, m.clone().operation_id);
+        files.insert(file, view_model_go.clone());
+      }
+
+      let view_model_java_opt = apis::generate_view_model_java(m.clone(), java_style, &config.java)
+        .filter(|_| config.enabled("java"));
+
+      for view_model_java in view_model_java_opt.iter() {
+        let dir = &format!("{}/{}/viewmodel", output, m.clone().operation_id);
+        let file = format!("{}/ViewModel.java", dir);
+        files.insert(file, view_model_java.clone());
+      }
 
-        for view_model_ts in view_model_ts_opt.iter() {
-          let dir = &format!("dist/{}/viewmodel", m.clone().operation_id);
-          fs::create_dir_all(dir)?;
-          let file = format!("{}/{}.ts", dir, m.clone().operation_id);
-          fs::write(file, view_model_ts)?;
+      for (type_name, content) in apis::additional_view_models(m.clone()) {
+        let dir = &format!("{}/{}/viewmodel", output, m.clone().operation_id);
+        let file = format!("{}/{}-{}", dir, m.clone().operation_id, type_name);
+        if config.enabled("scala") {
+          files.insert(
+            format!("{}.scala", file),
+            apis::generate_response_scala(
+              type_name.clone(),
+              content.clone(),
+              &config.scala,
+              &format!("{}.viewmodel", apis::package_name(&m.operation_id)),
+            ),
+          );
         }
+        if config.enabled("typescript") {
+          files.insert(
+            format!("{}.ts", file),
+            apis::generate_response_ts(type_name, content, &config.typescript),
+          );
+        }
+      }
+
+      let result_ts_opt =
+        apis::generate_result_ts(m.clone(), &error_types).filter(|_| config.enabled("typescript"));
+
+      for result_ts in result_ts_opt.iter() {
+        let dir = &format!("{}/{}/viewmodel", output, m.clone().operation_id);
+        let file = format!("{}/{}-Result.ts", dir, m.clone().operation_id);
+        files.insert(file, result_ts.clone());
+      }
+
+      // create examples
+      let command_example_opt =
+        sample::command_example(doc, &api.path, method_type, &m, sample_options)
+          .filter(|_| config.enabled("examples"));
+
+      for command_example in command_example_opt.iter() {
+        let dir = &format!("{}/{}/examples", output, m.clone().operation_id);
+        let file = format!("{}/command.json", dir);
+        files.insert(file, loader::to_json(command_example));
+      }
+
+      let view_model_example_opt =
+        sample::view_model_example(doc, &api.path, method_type, &m, sample_options)
+          .filter(|_| config.enabled("examples"));
+
+      for (_, view_model_example) in view_model_example_opt.iter() {
+        let dir = &format!("{}/{}/examples", output, m.clone().operation_id);
+        let file = format!("{}/viewmodel.json", dir);
+        files.insert(file, loader::to_json(view_model_example));
+      }
+
+      let arbitrary_scala_opt = apis::generate_arbitrary_scala(m.clone(), &config.scala)
+        .filter(|_| config.enabled("arbitrary"));
+
+      for arbitrary_scala in arbitrary_scala_opt.iter() {
+        let dir = &format!("{}/{}/test", output, m.clone().operation_id);
+        let file = format!("{}/{}Arbitraries.scala", dir, m.clone().operation_id);
+        files.insert(file, arbitrary_scala.clone());
+      }
+
+      let elm_opt = apis::generate_elm(m.clone(), &config.elm).filter(|_| config.enabled("elm"));
+
+      for elm in elm_opt.iter() {
+        let dir = &format!("{}/{}", output, m.clone().operation_id);
+        let file = format!("{}/{}.elm", dir, apis::elm_module_name(m.clone()));
+        files.insert(file, elm.clone());
       }
     }
+  }
 
-    let mut routes = apis
-      .iter()
-      .flat_map(|api| apis::to_play_routings(api.clone()))
-      .collect::<Vec<_>>();
-    routes.sort();
+  for (type_name, content) in error_types.clone() {
+    if config.enabled("scala") {
+      files.insert(
+        format!("{}/errors/{}.scala", output, type_name),
+        apis::generate_response_scala(type_name.clone(), content.clone(), &config.scala, "errors"),
+      );
+    }
+    if config.enabled("typescript") {
+      files.insert(
+        format!("{}/errors/{}.ts", output, type_name),
+        apis::generate_response_ts(type_name, content, &config.typescript),
+      );
+    }
+  }
 
-    fs::write("dist/routes", routes.join("\n"))?;
+  let mut routes = apis
+    .iter()
+    .flat_map(|api| apis::to_play_routings(api.clone(), &error_types))
+    .collect::<Vec<_>>();
+  // routes may be preceded by comment lines, order by the route itself
+  routes.sort_by(|a, b| a.lines().last().cmp(&b.lines().last()));
 
-    Ok(())
-  } else {
-    panic!("command use: ./draco-open-api input.yaml")
+  if config.enabled("routes") {
+    files.insert(format!("{}/routes", output), routes.join("\n"));
   }
+
+  files
 }
 
+#[cfg(test)]
 #[macro_use]
 extern crate maplit;
+/// A fresh directory under the system temp dir for one test, removed again when dropped.
+/// The process id and a counter keep parallel test runs apart.
+#[cfg(test)]
+pub struct TestDir(std::path::PathBuf);
+
+#[cfg(test)]
+impl TestDir {
+  pub fn new(name: &str) -> TestDir {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let path = env::temp_dir().join(format!(
+      "draco-{}-{}-{}",
+      name,
+      std::process::id(),
+      COUNT.fetch_add(1, Ordering::SeqCst)
+    ));
+    fs::create_dir_all(&path).unwrap();
+    TestDir(path)
+  }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TestDir {
+  type Target = std::path::Path;
+
+  fn deref(&self) -> &std::path::Path {
+    &self.0
+  }
+}
+
+#[cfg(test)]
+impl Drop for TestDir {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.0);
+  }
+}
+
+// `hashmap!` for the spec ordered maps of the model
+#[cfg(test)]
+macro_rules! linked_hash_map {
+  ($($key:expr => $value:expr),* $(,)?) => {{
+    let mut map = linked_hash_map::LinkedHashMap::new();
+    $(map.insert($key, $value);)*
+    map
+  }};
+}
 pub mod apis {
+  use crate::naming::{self, Naming, Target};
+  use crate::template::{object, string, Template};
+  use linked_hash_map::LinkedHashMap;
   use std::collections::HashMap;
+  use yaml_rust::Yaml;
 
   #[derive(PartialEq, Clone, Debug)]
   pub struct Api {
     pub path: String,
-    pub param_map: HashMap<String, ParamType>,
-    pub method_map: HashMap<String, Method>,
+    pub param_map: LinkedHashMap<String, ParamType>,
+    pub method_map: LinkedHashMap<String, Method>,
   }
 
   #[derive(PartialEq, Clone, Debug)]
@@ -94,12 +566,91 @@ pub mod apis {
     String,
   }
 
+  /// Options of a generation target, read from `draco.toml`.
+  #[derive(PartialEq, Clone, Debug, Default)]
+  pub struct TargetSettings {
+    pub package: Option<String>,
+    /// type overrides by content kind (`integer`, `date`, ...), or for one side only
+    /// (`command.date`, `view_model.date`); Elm expects `<type>Decoder` and `encode<Type>` in scope
+    pub types: HashMap<String, String>,
+    /// user templates replacing `SCALA_TEMPLATE` or `TYPESCRIPT_TEMPLATE` for one file type
+    /// (`command`, `viewmodel` or `response`)
+    pub templates: HashMap<String, Template>,
+    /// field naming, the target's convention when unset
+    pub naming: Option<Naming>,
+  }
+
+  // settings of the target plus whether a Command or a ViewModel is being generated
+  #[derive(Clone, Copy)]
+  struct Scope<'a> {
+    settings: &'a TargetSettings,
+    is_command: bool,
+  }
+
+  impl<'a> Scope<'a> {
+    fn command(settings: &'a TargetSettings) -> Scope<'a> {
+      Scope {
+        settings,
+        is_command: true,
+      }
+    }
+
+    fn view_model(settings: &'a TargetSettings) -> Scope<'a> {
+      Scope {
+        settings,
+        is_command: false,
+      }
+    }
+
+    fn override_for(&self, kind: &str) -> Option<&String> {
+      let side = if self.is_command {
+        "command"
+      } else {
+        "view_model"
+      };
+      self
+        .settings
+        .types
+        .get(&format!("{}.{}", side, kind))
+        .or_else(|| self.settings.types.get(kind))
+    }
+
+    fn is_overridden(&self, kind: &str) -> bool {
+      self.override_for(kind).is_some()
+    }
+
+    fn type_name(&self, kind: &str, default: &str) -> String {
+      self
+        .override_for(kind)
+        .cloned()
+        .unwrap_or_else(|| default.to_string())
+    }
+  }
+
+  #[derive(PartialEq, Clone, Copy, Debug)]
+  pub enum SpecVersion {
+    V30,
+    V31,
+  }
+
   #[derive(PartialEq, Clone, Debug)]
   pub struct Method {
     pub operation_id: String,
     pub summary: String,
+    /// content of the primary success response, see `primary_success_status`
     pub response_opt: Option<Content>,
     pub request_body_opt: Option<Content>,
+    pub request_media_type: Option<String>,
+    pub docs: Docs,
+    /// every response body by status code ("200", "2XX", "404", "default", ...), `None` for
+    /// statuses without content such as 204
+    pub response_map: LinkedHashMap<String, Option<Body>>,
+  }
+
+  #[derive(PartialEq, Clone, Debug)]
+  pub struct Body {
+    pub media_type: String,
+    pub content: Content,
   }
 
   #[derive(PartialEq, Clone, Debug)]
@@ -111,6 +662,24 @@ pub mod apis {
     Number,
     Boolean,
     Date,
+    Binary,
+    Enum(Vec<String>),
+    /// an integer, number or boolean `const`; string consts are single value enums
+    Const(yaml_rust::Yaml),
+    Union(Vec<Content>),
+    Tuple(Vec<Content>),
+  }
+
+  impl Content {
+    /// The type of a `Const` value, for targets without literal types.
+    pub fn const_type(value: &yaml_rust::Yaml) -> Content {
+      match value {
+        yaml_rust::Yaml::Integer(_) => Content::Integer,
+        yaml_rust::Yaml::Real(_) => Content::Number,
+        yaml_rust::Yaml::Boolean(_) => Content::Boolean,
+        _ => Content::String,
+      }
+    }
   }
 
   #[derive(PartialEq, Clone, Debug)]
@@ -118,10 +687,124 @@ pub mod apis {
     pub key: String,
     pub value: Content,
     pub or_null: bool,
+    /// Listed in the parent's `required`, optional properties may be left out of the payload.
+    pub required: bool,
+    pub constraints: Constraints,
+    pub docs: Docs,
+  }
+
+  impl Property {
+    /// An optional, non-nullable property without constraints or docs.
+    pub fn new(key: &str, value: Content) -> Property {
+      Property {
+        key: key.to_string(),
+        value,
+        or_null: false,
+        required: false,
+        constraints: Constraints::default(),
+        docs: Docs::default(),
+      }
+    }
+  }
+
+  /// Documentation of an operation or property, rendered as doc comments by the generators.
+  #[derive(PartialEq, Clone, Debug, Default)]
+  pub struct Docs {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub deprecated: bool,
+    pub example: Option<yaml_rust::Yaml>,
+    pub external_docs: Option<ExternalDocs>,
+  }
+
+  #[derive(PartialEq, Clone, Debug)]
+  pub struct ExternalDocs {
+    pub url: String,
+    pub description: Option<String>,
+  }
+
+  impl Docs {
+    /// Comment lines without markers: text first, then `@deprecated`, `@example` and `@see`.
+    pub fn lines(&self) -> Vec<String> {
+      let mut lines = vec![];
+      for text in vec![&self.title, &self.description].into_iter().flatten() {
+        if !lines.is_empty() {
+          lines.push("".to_string());
+        }
+        lines.extend(text.trim_end().lines().map(|line| line.to_string()));
+      }
+      if self.deprecated {
+        lines.push("@deprecated".to_string());
+      }
+      if let Some(example) = &self.example {
+        lines.push("@example".to_string());
+        lines.extend(
+          crate::loader::to_json(example)
+            .lines()
+            .map(|l| l.to_string()),
+        );
+      }
+      if let Some(external_docs) = &self.external_docs {
+        lines.push(match &external_docs.description {
+          Some(description) => format!("@see {} {}", external_docs.url, description),
+          None => format!("@see {}", external_docs.url),
+        });
+      }
+      lines
+    }
+  }
+
+  /// JSON Schema validation keywords of a property. Bounds stay `f64` since both integer and
+  /// number schemas use them.
+  #[derive(PartialEq, Clone, Debug, Default)]
+  pub struct Constraints {
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub multiple_of: Option<f64>,
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    pub pattern: Option<String>,
+    pub min_items: Option<u64>,
+    pub max_items: Option<u64>,
+    pub unique_items: bool,
+  }
+
+  impl Constraints {
+    pub fn is_empty(&self) -> bool {
+      self == &Constraints::default()
+    }
+
+    /// e.g. `minLength: 1, maxLength: 10`, used for doc comments.
+    pub fn describe(&self) -> String {
+      let mut descriptions = vec![];
+      let mut number = |name: &str, value: Option<f64>| {
+        if let Some(value) = value {
+          descriptions.push(format!("{}: {}", name, value));
+        }
+      };
+      number("minimum", self.minimum);
+      number("maximum", self.maximum);
+      number("multipleOf", self.multiple_of);
+      number("minLength", self.min_length.map(|n| n as f64));
+      number("maxLength", self.max_length.map(|n| n as f64));
+      number("minItems", self.min_items.map(|n| n as f64));
+      number("maxItems", self.max_items.map(|n| n as f64));
+      if let Some(pattern) = &self.pattern {
+        descriptions.push(format!("pattern: {}", pattern));
+      }
+      if self.unique_items {
+        descriptions.push("uniqueItems: true".to_string());
+      }
+      descriptions.join(", ")
+    }
   }
 
   pub fn from_yaml(yaml: &yaml_rust::Yaml) -> Vec<Api> {
     let paths = &yaml["paths"];
+    let version = match yaml["openapi"].as_str() {
+      Some(openapi) if openapi.starts_with("3.1") => SpecVersion::V31,
+      _ => SpecVersion::V30,
+    };
 
     fn create_param_tuple(param: yaml_rust::Yaml) -> (String, ParamType) {
       let schema_type = param["schema"]["type"].as_str();
@@ -133,9 +816,64 @@ pub mod apis {
           _ => panic!("unexpected schema type: {}", schema_type.unwrap_or("None")),
         },
       )
-    };
+    }
 
-    fn parse_properties(base_doument: yaml_rust::Yaml) -> Vec<Property> {
+    // strings make an enum, other values the union of their types; a null value belongs to
+    // nullable enums
+    fn create_enum_content(values: &[yaml_rust::Yaml]) -> Content {
+      let strings = values
+        .iter()
+        .filter_map(|value| value.as_str().map(|value| value.to_string()))
+        .collect::<Vec<_>>();
+      let mut contents = vec![];
+      if !strings.is_empty() {
+        contents.push(Content::Enum(strings));
+      }
+      for value in values {
+        if let yaml_rust::Yaml::Integer(_)
+        | yaml_rust::Yaml::Real(_)
+        | yaml_rust::Yaml::Boolean(_) = value
+        {
+          let content = Content::const_type(value);
+          if !contents.contains(&content) {
+            contents.push(content);
+          }
+        }
+      }
+      match contents.len() {
+        0 => Content::String,
+        1 => contents.remove(0),
+        _ => Content::Union(contents),
+      }
+    }
+
+    // formats other than date and binary (date-time, uuid, email, ...) stay plain strings
+    fn create_string_content(base_document: yaml_rust::Yaml) -> Content {
+      match base_document["enum"].as_vec() {
+        Some(values) => create_enum_content(values),
+        None => match base_document["format"].as_str() {
+          Some("date") => Content::Date,
+          Some("binary") => Content::Binary,
+          _ => Content::String,
+        },
+      }
+    }
+
+    // a single value enum of a number or boolean schema, as the emitter writes 3.0 consts
+    fn create_scalar_content(base_document: &yaml_rust::Yaml, content: Content) -> Content {
+      let values = base_document["enum"]
+        .as_vec()
+        .into_iter()
+        .flatten()
+        .filter(|value| !value.is_null())
+        .collect::<Vec<_>>();
+      match values.as_slice() {
+        [value] if Content::const_type(value) == content => Content::Const((*value).clone()),
+        _ => content,
+      }
+    }
+
+    fn parse_properties(base_doument: yaml_rust::Yaml, version: SpecVersion) -> Vec<Property> {
       let property_keys = match base_doument["properties"].as_hash() {
         Some(hash) => hash
           .keys()
@@ -147,117 +885,252 @@ pub mod apis {
       property_keys
         .into_iter()
         .map(|key| {
-          let prop_type = base_doument["properties"][key]["type"].clone();
+          let property = base_doument["properties"][key].clone();
 
           Property {
             key: key.to_string(),
-            value: {
-              match prop_type.as_str() {
-                Some("string") => base_doument["properties"][key]["format"]
-                  .clone()
-                  .as_str()
-                  .map(|format| {
-                    if format == "date" {
-                      Content::Date
-                    } else {
-                      panic!("unsupported format type: {}", format)
-                    }
-                  })
-                  .unwrap_or(Content::String),
-                Some("integer") => Content::Integer,
-                Some("number") => Content::Number,
-                Some("boolean") => Content::Boolean,
-                // type is list
-                None => {
-                  let prop_types = prop_type
-                    .clone()
-                    .into_iter()
-                    .filter(|t| t.as_str() != Some("null"))
-                    .collect::<Vec<_>>();
-                  if prop_types.len() == 1 {
-                    match prop_types[0].as_str() {
-                      Some("string") => base_doument["properties"][key]["format"]
-                        .clone()
-                        .as_str()
-                        .map(|format| {
-                          if format == "date" {
-                            Content::Date
-                          } else {
-                            panic!("unsupported format type: {}", format)
-                          }
-                        })
-                        .unwrap_or(Content::String),
-                      Some("integer") => Content::Integer,
-                      Some("number") => Content::Number,
-                      Some("boolean") => Content::Boolean,
-                      Some("object") => {
-                        Content::Object(parse_properties(base_doument["properties"][key].clone()))
-                      }
-                      _ => panic!(
-                        "unsuppoted property type: ({}: {})",
-                        key,
-                        prop_types[0].as_str().unwrap_or("None")
-                      ),
-                    }
-                  } else {
-                    panic!("property type must have num of 2. info: {:?}", prop_types)
-                  }
-                }
-                Some("object") | Some("array") => {
-                  create_schema(base_doument["properties"][key].clone())
-                    .expect(&format!("fail to create nested object, key: {}", key))
-                }
-                _ => panic!(
-                  "unsuppoted nested property type: ({}: {})",
-                  key,
-                  prop_type.as_str().unwrap_or("None")
-                ),
-              }
-            },
-            or_null: prop_type.into_iter().any(|t| t.as_str() == Some("null")),
+            value: create_schema(property.clone(), version).unwrap_or_else(|| {
+              panic!(
+                "unsuppoted property type: ({}: {:?})",
+                key, property["type"]
+              )
+            }),
+            or_null: is_nullable(&property, version),
+            required: base_doument["required"]
+              .as_vec()
+              .map(|required| required.iter().any(|name| name.as_str() == Some(key)))
+              .unwrap_or(false),
+            constraints: create_constraints(&property),
+            docs: create_docs(&property),
           }
         })
         .collect::<Vec<_>>()
     }
 
-    fn create_schema(base_document: yaml_rust::Yaml) -> Option<Content> {
-      base_document["type"]
-        .clone()
-        .as_str()
-        .and_then(|schema_type| match schema_type {
-          "object" => Some(Content::Object(parse_properties(base_document))),
-          "array" => create_schema(base_document["items"].clone())
+    fn create_docs(base_document: &yaml_rust::Yaml) -> Docs {
+      let text = |yaml: &yaml_rust::Yaml| {
+        yaml
+          .as_str()
+          .filter(|text| !text.trim().is_empty())
+          .map(|text| text.to_string())
+      };
+      Docs {
+        title: text(&base_document["title"]),
+        description: text(&base_document["description"]),
+        deprecated: base_document["deprecated"].as_bool() == Some(true),
+        // 3.1 schemas list `examples`, the first one stands for the property
+        example: match (&base_document["example"], &base_document["examples"]) {
+          (yaml_rust::Yaml::BadValue, yaml_rust::Yaml::Array(examples)) => {
+            examples.first().cloned()
+          }
+          (yaml_rust::Yaml::BadValue, _) => None,
+          (example, _) => Some(example.clone()),
+        },
+        external_docs: text(&base_document["externalDocs"]["url"]).map(|url| ExternalDocs {
+          url,
+          description: text(&base_document["externalDocs"]["description"]),
+        }),
+      }
+    }
+
+    fn create_constraints(base_document: &yaml_rust::Yaml) -> Constraints {
+      let number = |key: &str| match &base_document[key] {
+        yaml_rust::Yaml::Integer(value) => Some(*value as f64),
+        yaml_rust::Yaml::Real(value) => value.parse().ok(),
+        _ => None,
+      };
+      let count = |key: &str| base_document[key].as_i64().map(|value| value as u64);
+      Constraints {
+        minimum: number("minimum"),
+        maximum: number("maximum"),
+        multiple_of: number("multipleOf"),
+        min_length: count("minLength"),
+        max_length: count("maxLength"),
+        pattern: base_document["pattern"].as_str().map(|p| p.to_string()),
+        min_items: count("minItems"),
+        max_items: count("maxItems"),
+        unique_items: base_document["uniqueItems"].as_bool() == Some(true),
+      }
+    }
+
+    fn union_members(base_document: &yaml_rust::Yaml) -> Option<&Vec<yaml_rust::Yaml>> {
+      base_document["anyOf"]
+        .as_vec()
+        .or_else(|| base_document["oneOf"].as_vec())
+    }
+
+    fn is_null_schema(base_document: &yaml_rust::Yaml) -> bool {
+      base_document["type"].as_str() == Some("null")
+    }
+
+    // 3.0 marks nullable schemas with `nullable: true`, 3.1 lists 'null' in the type array or
+    // among the `anyOf` members. Type arrays are accepted for 3.0 documents as well.
+    fn is_nullable(base_document: &yaml_rust::Yaml, version: SpecVersion) -> bool {
+      let null_in_type = base_document["type"]
+        .as_vec()
+        .map(|types| types.iter().any(|t| t.as_str() == Some("null")))
+        .unwrap_or(false)
+        || union_members(base_document)
+          .map(|members| members.iter().any(is_null_schema))
+          .unwrap_or(false);
+      match version {
+        SpecVersion::V30 => null_in_type || base_document["nullable"].as_bool() == Some(true),
+        SpecVersion::V31 => null_in_type,
+      }
+    }
+
+    fn create_typed_schema(
+      base_document: &yaml_rust::Yaml,
+      schema_type: &str,
+      version: SpecVersion,
+    ) -> Option<Content> {
+      match schema_type {
+        "object" => Some(Content::Object(parse_properties(
+          base_document.clone(),
+          version,
+        ))),
+        "array" => match base_document["prefixItems"].as_vec() {
+          Some(items) if version == SpecVersion::V31 => items
+            .iter()
+            .map(|item| create_schema(item.clone(), version))
+            .collect::<Option<Vec<_>>>()
+            .map(Content::Tuple),
+          _ => create_schema(base_document["items"].clone(), version)
             .map(|items| Content::Array(Box::new(items))),
-          "string" => Some(Content::String),
-          "integer" => Some(Content::Integer),
-          "number" => Some(Content::Number),
-          "boolean" => Some(Content::Boolean),
-          _ => None,
-        })
+        },
+        "string" => Some(create_string_content(base_document.clone())),
+        "integer" => Some(create_scalar_content(base_document, Content::Integer)),
+        "number" => Some(create_scalar_content(base_document, Content::Number)),
+        "boolean" => Some(create_scalar_content(base_document, Content::Boolean)),
+        _ => None,
+      }
+    }
+
+    fn create_schema(base_document: yaml_rust::Yaml, version: SpecVersion) -> Option<Content> {
+      // the loader keeps recursive schemas as a `$ref` where they repeat, typed as a free-form
+      // object from there on
+      if base_document["$ref"].as_str().is_some() {
+        return Some(Content::Object(vec![]));
+      }
+      match &base_document["const"] {
+        yaml_rust::Yaml::String(value) => return Some(Content::Enum(vec![value.clone()])),
+        value @ yaml_rust::Yaml::Integer(_)
+        | value @ yaml_rust::Yaml::Real(_)
+        | value @ yaml_rust::Yaml::Boolean(_) => return Some(Content::Const(value.clone())),
+        _ => {}
+      }
+      // members a type array can not hold together, as the emitter writes them
+      if let Some(members) = union_members(&base_document) {
+        let contents = members
+          .iter()
+          .filter(|member| !is_null_schema(member))
+          .map(|member| create_schema(member.clone(), version))
+          .collect::<Option<Vec<_>>>()?;
+        return if contents.len() > 1 {
+          Some(Content::Union(contents))
+        } else {
+          contents.into_iter().next()
+        };
+      }
+
+      match &base_document["type"] {
+        yaml_rust::Yaml::String(schema_type) => {
+          create_typed_schema(&base_document, schema_type, version)
+        }
+        yaml_rust::Yaml::Array(schema_types) => {
+          let contents = schema_types
+            .iter()
+            .filter(|t| t.as_str() != Some("null"))
+            .map(|t| {
+              t.as_str()
+                .and_then(|t| create_typed_schema(&base_document, t, version))
+            })
+            .collect::<Option<Vec<_>>>()?;
+          if contents.len() > 1 {
+            Some(Content::Union(contents))
+          } else {
+            contents.into_iter().next()
+          }
+        }
+        _ => base_document["enum"]
+          .as_vec()
+          .map(|values| create_enum_content(values)),
+      }
+    }
+
+    // JSON is preferred, then forms, then whatever the operation lists first.
+    fn create_body(content: &yaml_rust::Yaml, version: SpecVersion) -> Option<Body> {
+      let media_types = content
+        .as_hash()
+        .into_iter()
+        .flatten()
+        .filter_map(|(media_type, media)| media_type.as_str().map(|m| (m.to_string(), media)))
+        .collect::<Vec<_>>();
+      let priority = |media_type: &str| {
+        if is_json_media_type(media_type) {
+          0
+        } else if is_form_media_type(media_type) {
+          1
+        } else {
+          2
+        }
+      };
+      let (media_type, media) = media_types
+        .iter()
+        .enumerate()
+        .min_by_key(|(index, (media_type, _))| (priority(media_type), *index))
+        .map(|(_, media)| media.clone())?;
+
+      let content = create_schema(media["schema"].clone(), version).or_else(|| {
+        if media_type.starts_with("text/") {
+          Some(Content::String)
+        } else if is_json_media_type(&media_type) || is_form_media_type(&media_type) {
+          None
+        } else {
+          Some(Content::Binary)
+        }
+      })?;
+      Some(Body {
+        media_type,
+        content,
+      })
     }
 
-    fn create_method(method: yaml_rust::Yaml) -> Method {
-      let request_body_schema =
-        method["requestBody"]["content"]["application/json"]["schema"].clone();
+    fn create_method(method: yaml_rust::Yaml, version: SpecVersion) -> Method {
+      let request_body = create_body(&method["requestBody"]["content"], version);
 
-      let response_schema =
-        method["responses"]["200"]["content"]["application/json"]["schema"].clone();
+      let response_map = method["responses"]
+        .as_hash()
+        .into_iter()
+        .flatten()
+        .map(|(status, response)| {
+          let status = match status {
+            yaml_rust::Yaml::Integer(status) => status.to_string(),
+            status => status
+              .as_str()
+              .expect("can not get response status")
+              .to_string(),
+          };
+          (status, create_body(&response["content"], version))
+        })
+        .collect::<LinkedHashMap<_, _>>();
 
+      let operation_id = method["operationId"].as_str().unwrap().to_string();
       Method {
-        operation_id: method["operationId"].as_str().unwrap().to_string(),
+        // `lint` only warns about an empty summary, the operationId stands in for it
         summary: method["summary"]
           .as_str()
-          .and_then(|summary| {
-            if summary.trim().is_empty() {
-              None
-            } else {
-              Some(summary)
-            }
-          })
-          .expect("summary is empty")
-          .to_string(),
-        response_opt: create_schema(response_schema),
-        request_body_opt: create_schema(request_body_schema),
+          .filter(|summary| !summary.trim().is_empty())
+          .map(|summary| summary.to_string())
+          .unwrap_or_else(|| operation_id.clone()),
+        operation_id,
+        response_opt: primary_success_status(&response_map)
+          .and_then(|status| response_map[&status].as_ref())
+          .map(|body| body.content.clone()),
+        request_media_type: request_body.clone().map(|body| body.media_type),
+        docs: create_docs(&method),
+        request_body_opt: request_body.map(|body| body.content),
+        response_map,
       }
     }
 
@@ -278,7 +1151,7 @@ pub mod apis {
             .clone()
             .into_iter()
             .map(create_param_tuple)
-            .collect::<HashMap<_, _>>(),
+            .collect::<LinkedHashMap<_, _>>(),
           method_map: path_methods
             .map(|method| {
               method
@@ -289,23 +1162,140 @@ pub mod apis {
             .map(|method| {
               (
                 method.to_string(),
-                create_method(paths[path][method].clone()),
+                create_method(paths[path][method].clone(), version),
               )
             })
-            .collect::<HashMap<_, _>>(),
+            .collect::<LinkedHashMap<_, _>>(),
         }
       })
       .collect()
   }
-  pub fn nomalize_play_variable_path(path: String) -> String {
-    use regex::Regex;
+  fn essence(media_type: &str) -> String {
+    media_type
+      .split(';')
+      .next()
+      .unwrap_or("")
+      .trim()
+      .to_lowercase()
+  }
 
-    let modify_identifer = {
-      let variable_reg = Regex::new(r"^\{.*\}$").unwrap();
-      let blace_reg = Regex::new(r"[{}]").unwrap();
+  /// `application/json` and any structured `+json` type such as `application/problem+json`.
+  pub fn is_json_media_type(media_type: &str) -> bool {
+    let essence = essence(media_type);
+    essence == "application/json" || essence.ends_with("+json")
+  }
 
-      move |variable: String| {
-        if variable_reg.is_match(&variable) {
+  pub fn is_form_media_type(media_type: &str) -> bool {
+    let essence = essence(media_type);
+    essence == "multipart/form-data" || essence == "application/x-www-form-urlencoded"
+  }
+
+  pub fn is_success_status(status: &str) -> bool {
+    status.starts_with('2')
+  }
+
+  pub fn is_error_status(status: &str) -> bool {
+    status.starts_with('4') || status.starts_with('5') || status == "default"
+  }
+
+  /// "200" when it has content, otherwise the lowest success status with content, falling back
+  /// to the lowest one without.
+  pub fn primary_success_status(
+    response_map: &LinkedHashMap<String, Option<Body>>,
+  ) -> Option<String> {
+    response_map
+      .iter()
+      .filter(|(status, _)| is_success_status(status))
+      .map(|(status, body)| (body.is_none(), status != "200", status.clone()))
+      .min()
+      .map(|(_, _, status)| status)
+  }
+
+  /// ViewModels for the success responses other than the primary one, e.g. `ViewModel201`.
+  pub fn additional_view_models(method: Method) -> Vec<(String, Content)> {
+    let primary_status = primary_success_status(&method.response_map);
+    let mut view_models = method
+      .response_map
+      .into_iter()
+      .filter(|(status, _)| is_success_status(status) && Some(status) != primary_status.as_ref())
+      .filter_map(|(status, body)| body.map(|body| (format!("ViewModel{}", status), body.content)))
+      .collect::<Vec<_>>();
+    view_models.sort_by(|a, b| a.0.cmp(&b.0));
+    view_models
+  }
+
+  /// Error response types shared between operations, e.g. `ErrorResponse404`.
+  /// Identical contents are generated once, conflicting ones get a numbered suffix.
+  pub fn error_types(apis: Vec<Api>) -> Vec<(String, Content)> {
+    let mut error_types: Vec<(String, Content)> = vec![];
+    for api in apis {
+      let mut methods = api.method_map.into_iter().collect::<Vec<_>>();
+      methods.sort_by(|a, b| a.0.cmp(&b.0));
+      for (_, method) in methods {
+        let mut responses = method
+          .response_map
+          .into_iter()
+          .filter(|(status, _)| is_error_status(status))
+          .filter_map(|(status, body)| body.map(|body| (status, body.content)))
+          .collect::<Vec<_>>();
+        responses.sort_by(|a, b| a.0.cmp(&b.0));
+        for (status, content) in responses {
+          if error_types.iter().any(|(_, error)| error == &content) {
+            continue;
+          }
+          let base_name = format!("ErrorResponse{}", head_uppercase(status));
+          let mut name = base_name.clone();
+          let mut suffix = 1;
+          while error_types
+            .iter()
+            .any(|(error_name, _)| error_name == &name)
+          {
+            suffix += 1;
+            name = format!("{}_{}", base_name, suffix);
+          }
+          error_types.push((name, content));
+        }
+      }
+    }
+    error_types
+  }
+  /// Error statuses of an operation with the `error_types` name of their body, `None` for
+  /// statuses without content.
+  pub fn operation_errors(
+    method: &Method,
+    error_types: &[(String, Content)],
+  ) -> Vec<(String, Option<String>)> {
+    let mut errors = method
+      .response_map
+      .iter()
+      .filter(|(status, _)| is_error_status(status))
+      .map(|(status, body)| {
+        let type_name = body.as_ref().and_then(|body| {
+          error_types
+            .iter()
+            .find(|(_, content)| content == &body.content)
+            .map(|(name, _)| name.clone())
+        });
+        (status.clone(), type_name)
+      })
+      .collect::<Vec<_>>();
+    errors.sort();
+    errors
+  }
+
+  pub fn from_document(yaml: &yaml_rust::Yaml) -> Vec<Api> {
+    from_yaml(&crate::swagger2::as_openapi3(yaml))
+  }
+
+  pub fn nomalize_play_variable_path(path: String) -> String {
+    use regex::Regex;
+
+    let modify_identifer = {
+      let variable_reg = Regex::new(r"^\{.*\}$").unwrap();
+      let blace_reg = Regex::new(r"[{}]").unwrap();
+
+      move |variable: String| {
+        if variable_reg.is_match(&variable) {
           format!(":{}", blace_reg.replace_all(&variable, ""))
         } else {
           variable
@@ -320,483 +1310,4265 @@ pub mod apis {
       .join("/")
   }
 
-  pub fn to_play_routings(api: Api) -> Vec<String> {
+  /// Play routes, each preceded by the operation docs and its error responses as comments.
+  pub fn to_play_routings(api: Api, error_types: &[(String, Content)]) -> Vec<String> {
     api
       .method_map
       .clone()
-      .keys()
-      .map(|method_type| {
-        format!(
-          "{} {} {{Method Name}}({})",
-          match &method_type[..] {
-            "get" => "GET",
-            "post" => "POST",
-            "put" => "PUT",
-            "delete" => "DELETE",
-            m => panic!("unsupported method type {}", m),
-          },
-          nomalize_play_variable_path(api.path.clone()),
-          api
-            .clone()
-            .param_map
-            .into_iter()
-            .map(|(param, param_type)| format!(
-              "{}: {}",
-              param,
-              match param_type {
-                ParamType::String => "String",
-                ParamType::Integer => "Long",
-              }
-            ))
-            .collect::<Vec<_>>()
-            .join(", ")
-        )
+      .iter()
+      .map(|(method_type, method)| {
+        let mut comments = method
+          .docs
+          .lines()
+          .into_iter()
+          .map(|line| format!("# {}\n", line).replace("# \n", "#\n"))
+          .collect::<String>();
+        let errors = operation_errors(method, error_types)
+          .into_iter()
+          .map(|(status, type_name)| match type_name {
+            Some(type_name) => format!("{} {}", status, type_name),
+            None => status,
+          })
+          .collect::<Vec<_>>();
+        if !errors.is_empty() {
+          comments += &format!("# errors: {}\n", errors.join(", "));
+        }
+        comments
+          + &format!(
+            "{} {} {{Method Name}}({})",
+            match &method_type[..] {
+              "get" => "GET",
+              "post" => "POST",
+              "put" => "PUT",
+              "delete" => "DELETE",
+              m => panic!("unsupported method type {}", m),
+            },
+            nomalize_play_variable_path(api.path.clone()),
+            api
+              .clone()
+              .param_map
+              .into_iter()
+              .map(|(param, param_type)| format!(
+                "{}: {}",
+                param,
+                match param_type {
+                  ParamType::String => "String",
+                  ParamType::Integer => "Long",
+                }
+              ))
+              .collect::<Vec<_>>()
+              .join(", ")
+          )
       })
       .collect()
   }
 
+  // JSDoc and Scaladoc share the `/** ... */` layout
+  fn doc_comment(indent: &str, lines: Vec<String>) -> String {
+    match lines.len() {
+      0 => "".to_string(),
+      1 if !lines[0].starts_with('@') => format!("{}/** {} */\n", indent, lines[0]),
+      _ => format!(
+        "{0}/**\n{1}{0} */\n",
+        indent,
+        lines
+          .iter()
+          .map(|line| format!("{} * {}\n", indent, line).replace(" * \n", " *\n"))
+          .collect::<String>()
+      ),
+    }
+  }
+
+  fn scaladoc(docs: &Docs, properties: &[Property], naming: Option<Naming>) -> String {
+    let mut lines = docs.lines();
+    for property in properties {
+      let property_lines = property.docs.lines();
+      if property_lines.is_empty() {
+        continue;
+      }
+      if !lines.is_empty() && !lines.last().unwrap().starts_with("@param") {
+        lines.push("".to_string());
+      }
+      lines.push(format!(
+        "@param {} {}",
+        naming::field_name(&property.key, Target::Scala, naming),
+        property_lines.join(" ")
+      ));
+    }
+    doc_comment("", lines)
+  }
+
   fn head_uppercase(str: String) -> String {
-    str[0..1].to_uppercase() + &str[1..]
+    naming::head_uppercase(&str)
+  }
+
+  // `package` under the configured base package, if any
+  fn configured_package(settings: &TargetSettings, package: &str) -> Option<String> {
+    settings
+      .package
+      .as_ref()
+      .map(|base| format!("{}.{}", base, package))
+  }
+
+  fn scala_package(settings: &TargetSettings, package: &str) -> String {
+    match configured_package(settings, package) {
+      Some(package) => format!("package {}\n\n", package),
+      None => "".to_string(),
+    }
+  }
+
+  /// Default template of the Scala files. The view has `package`, `type_name`, `classes` (each
+  /// with `name`, `doc`, `properties` and `reads`, a companion object with the play-json Reads
+  /// and, for renamed fields, Writes), or `doc` and `type` when the root is not an object, plus
+  /// the target independent `content` and `method`.
+  pub const SCALA_TEMPLATE: &str = "{{#if package}}package {{package}}
+
+{{/if}}{{#each classes}}{{#if separated}}
+{{/if}}{{doc}}case class {{name}}({{#each properties}}{{name}}: {{type}}{{#unless @last}},
+{{/unless}}{{/each}})
+{{#if reads}}{{reads}}
+{{/if}}{{else}}{{doc}}{{type}}{{/each}}";
+
+  /// Default template of the TypeScript files, with the view of `SCALA_TEMPLATE` minus `package`
+  /// and `reads`, plus the `keys` of classes whose field names differ from the JSON keys and the
+  /// Zod `schema` of types having constraints.
+  pub const TYPESCRIPT_TEMPLATE: &str = "{{#if schema}}import { z } from \"zod\";
+
+{{/if}}{{doc}}{{#each classes}}{{#if separated}}
+{{/if}}export type {{name}}={{{#each properties}}{{doc}}{{name}}: {{type}}{{#unless @last}};
+{{/unless}}{{/each}}}
+{{#if keys}}export const {{name}}Keys={{keys}} as const;
+{{/if}}{{else}}export type {{type_name}}={{type}}
+{{/each}}{{#if schema}}
+export const {{type_name}}Schema = {{schema}};
+{{/if}}";
+
+  fn render(settings: &TargetSettings, file_type: &str, default: &str, view: &Yaml) -> String {
+    match settings.templates.get(file_type) {
+      Some(template) => template.render(view),
+      None => Template::parse(default)
+        .expect("built-in template")
+        .render(view),
+    }
+  }
+
+  fn optional(value: Option<&str>) -> Yaml {
+    value.map(string).unwrap_or(Yaml::Null)
+  }
+
+  fn docs_view(docs: &Docs) -> Yaml {
+    object(vec![
+      ("title", optional(docs.title.as_deref())),
+      ("description", optional(docs.description.as_deref())),
+      ("deprecated", Yaml::Boolean(docs.deprecated)),
+      (
+        "lines",
+        Yaml::Array(docs.lines().into_iter().map(Yaml::String).collect()),
+      ),
+    ])
+  }
+
+  fn constraints_view(constraints: &Constraints) -> Yaml {
+    let number = |value: Option<f64>| {
+      value
+        .map(|value| Yaml::Real(value.to_string()))
+        .unwrap_or(Yaml::Null)
+    };
+    let count = |value: Option<u64>| {
+      value
+        .map(|value| Yaml::Integer(value as i64))
+        .unwrap_or(Yaml::Null)
+    };
+    object(vec![
+      ("minimum", number(constraints.minimum)),
+      ("maximum", number(constraints.maximum)),
+      ("multiple_of", number(constraints.multiple_of)),
+      ("min_length", count(constraints.min_length)),
+      ("max_length", count(constraints.max_length)),
+      ("pattern", optional(constraints.pattern.as_deref())),
+      ("min_items", count(constraints.min_items)),
+      ("max_items", count(constraints.max_items)),
+      ("unique_items", Yaml::Boolean(constraints.unique_items)),
+      ("description", Yaml::String(constraints.describe())),
+    ])
+  }
+
+  // target specific entries like `name` and `type` come first
+  fn property_view(property: &Property, mut entries: Vec<(&str, Yaml)>) -> Yaml {
+    entries.extend(vec![
+      ("key", string(&property.key)),
+      ("or_null", Yaml::Boolean(property.or_null)),
+      ("doc", Yaml::String(doc_comment("", property.docs.lines()))),
+      ("docs", docs_view(&property.docs)),
+      ("constraints", constraints_view(&property.constraints)),
+      ("content", content_view(&property.value)),
+    ]);
+    object(entries)
   }
 
-  fn content_to_string_scala(class_name: String, content: Content, is_command: bool) -> String {
+  /// Target independent view of a content, e.g. `{kind: array, items: {kind: string}}`.
+  fn content_view(content: &Content) -> Yaml {
+    let kind = |kind: &str| ("kind", string(kind));
+    let contents = |contents: &[Content]| Yaml::Array(contents.iter().map(content_view).collect());
     match content {
-      Content::Object(properties) => {
-        format!(
-          "case class {}({})",
-          class_name,
+      Content::Object(properties) => object(vec![
+        kind("object"),
+        (
+          "properties",
+          Yaml::Array(
+            properties
+              .iter()
+              .map(|property| property_view(property, vec![]))
+              .collect(),
+          ),
+        ),
+      ]),
+      Content::Array(items) => object(vec![kind("array"), ("items", content_view(items))]),
+      Content::String => object(vec![kind("string")]),
+      Content::Integer => object(vec![kind("integer")]),
+      Content::Number => object(vec![kind("number")]),
+      Content::Boolean => object(vec![kind("boolean")]),
+      Content::Date => object(vec![kind("date")]),
+      Content::Binary => object(vec![kind("binary")]),
+      Content::Enum(values) => object(vec![
+        kind("enum"),
+        (
+          "values",
+          Yaml::Array(values.iter().map(|value| string(value)).collect()),
+        ),
+      ]),
+      Content::Const(value) => object(vec![kind("const"), ("value", value.clone())]),
+      Content::Union(union) => object(vec![kind("union"), ("contents", contents(union))]),
+      Content::Tuple(tuple) => object(vec![kind("tuple"), ("contents", contents(tuple))]),
+    }
+  }
+
+  fn method_view(method: &Method) -> Yaml {
+    let content =
+      |content: &Option<Content>| content.as_ref().map(content_view).unwrap_or(Yaml::Null);
+    object(vec![
+      ("operation_id", string(&method.operation_id)),
+      ("summary", string(&method.summary)),
+      ("package", Yaml::String(package_name(&method.operation_id))),
+      (
+        "request_media_type",
+        optional(method.request_media_type.as_deref()),
+      ),
+      ("docs", docs_view(&method.docs)),
+      ("command", content(&method.request_body_opt)),
+      ("view_model", content(&method.response_opt)),
+    ])
+  }
+
+  // the operation's docs go on the root case class, next to the @param lines of its properties
+  fn scala_view(
+    class_name: String,
+    content: Content,
+    scope: Scope,
+    docs: &Docs,
+    package: Option<String>,
+    method: Option<&Method>,
+  ) -> Yaml {
+    let mut classes = vec![];
+    let (doc, type_expression) = match scala_root_class(&class_name, &content) {
+      Some((root_name, properties)) => {
+        scala_classes(
+          root_name,
+          properties.clone(),
+          scope,
+          scaladoc(docs, properties, scope.settings.naming),
+          false,
+          // a case class writes its field names, so renamed fields need the keys mapped back,
+          // and the Writes of a class need those of its nested classes
+          is_renamed_scala(properties, scope.settings.naming),
+          &mut classes,
+        );
+        ("".to_string(), "".to_string())
+      }
+      None => (
+        doc_comment("", docs.lines()),
+        content_to_string_scala(class_name.clone(), content.clone(), scope),
+      ),
+    };
+    object(vec![
+      ("package", package.map(Yaml::String).unwrap_or(Yaml::Null)),
+      ("type_name", Yaml::String(class_name)),
+      ("doc", Yaml::String(doc)),
+      ("type", Yaml::String(type_expression)),
+      ("classes", Yaml::Array(classes)),
+      ("content", content_view(&content)),
+      ("method", method.map(method_view).unwrap_or(Yaml::Null)),
+    ])
+  }
+
+  // the case class at the root of a content, `<class_name>Item` for a list of objects
+  fn scala_root_class<'a>(
+    class_name: &str,
+    content: &'a Content,
+  ) -> Option<(String, &'a Vec<Property>)> {
+    match content {
+      Content::Object(properties) => Some((class_name.to_string(), properties)),
+      Content::Array(items) => match &**items {
+        Content::Object(properties) => Some((format!("{}Item", class_name), properties)),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  // nested case classes follow their parent; `separated` puts a blank line between siblings
+  fn scala_classes(
+    class_name: String,
+    properties: Vec<Property>,
+    scope: Scope,
+    doc: String,
+    separated: bool,
+    writes: bool,
+    classes: &mut Vec<Yaml>,
+  ) {
+    let scala_types = properties
+      .iter()
+      .map(|property| match &property.value {
+        Content::Object(_) => to_pascal_case(property.key.to_string()),
+        Content::Array(items) if matches!(**items, Content::Object(_)) => {
+          format!("Seq[{}]", to_pascal_case(property.key.to_string()))
+        }
+        _ => content_to_string_scala("".to_string(), property.value.clone(), scope),
+      })
+      .collect::<Vec<_>>();
+    // uploaded files are bound from the multipart form, not read from JSON
+    let uploads = scope.is_command
+      && properties.iter().any(|property| match &property.value {
+        Content::Binary => true,
+        Content::Array(items) => matches!(**items, Content::Binary),
+        _ => false,
+      });
+    let names = properties
+      .iter()
+      .map(|property| naming::field_name(&property.key, Target::Scala, scope.settings.naming))
+      .collect::<Vec<_>>();
+    let mut implicits = vec![];
+    if !uploads {
+      implicits.push(scala_reads(&class_name, &properties, &scala_types, scope));
+    }
+    if writes {
+      implicits.push(scala_writes(&class_name, &properties, &scala_types, &names));
+    }
+    let reads = if implicits.is_empty() {
+      "".to_string()
+    } else {
+      format!(
+        "object {} {{\n  import play.api.libs.functional.syntax._\n  import play.api.libs.json._\n\n{}\n}}",
+        class_name,
+        implicits.join("\n")
+      )
+    };
+    classes.push(object(vec![
+      ("name", Yaml::String(class_name)),
+      ("doc", Yaml::String(doc)),
+      ("separated", Yaml::Boolean(separated)),
+      (
+        "properties",
+        Yaml::Array(
           properties
-            .clone()
-            .into_iter()
-            .map(|property| {
-              format!(
-                "{}: {}",
-                property.key,
-                match property.value {
-                  Content::Object(_) => head_uppercase(property.key.to_string()),
-                  _ => content_to_string_scala("".to_string(), property.value, is_command),
-                }
-              )
-            })
-            .collect::<Vec<_>>()
-            .join(",\n")
-        ) + "\n"
-          + &properties
-            .into_iter()
-            .filter(|property| match property.value {
-              Content::Object(_) => true,
-              _ => false,
-            })
-            .map(|property| {
-              content_to_string_scala(
-                head_uppercase(property.key.to_string()),
-                property.value,
-                is_command,
+            .iter()
+            .zip(scala_types.iter())
+            .zip(names.iter())
+            .map(|((property, scala_type), name)| {
+              property_view(
+                property,
+                vec![
+                  ("name", string(name)),
+                  (
+                    "type",
+                    Yaml::String(if property.or_null {
+                      format!("Option[{}]", scala_type)
+                    } else {
+                      scala_type.clone()
+                    }),
+                  ),
+                  ("value_type", string(scala_type)),
+                ],
               )
             })
-            .collect::<Vec<_>>()
-            .join("\n")
-      }
-      Content::String => "String".to_string(),
-      Content::Integer => "Int or Long".to_string(),
-      Content::Number => "Float".to_string(),
-      Content::Boolean => "Boolean".to_string(),
-      Content::Date => (if is_command {
-        "ZonedDateTime"
-      } else {
-        "Instant"
-      })
-      .to_string(),
+            .collect(),
+        ),
+      ),
+      ("reads", Yaml::String(reads)),
+    ]));
+    let nested = properties
+      .into_iter()
+      .filter_map(|property| match property.value {
+        Content::Object(nested) => Some((property.key, nested)),
+        Content::Array(items) => match *items {
+          Content::Object(nested) => Some((property.key, nested)),
+          _ => None,
+        },
+        _ => None,
+      });
+    for (index, (key, nested)) in nested.enumerate() {
+      let doc = scaladoc(&Docs::default(), &nested, scope.settings.naming);
+      scala_classes(
+        to_pascal_case(key),
+        nested,
+        scope,
+        doc,
+        index > 0,
+        writes,
+        classes,
+      );
+    }
+  }
+
+  fn content_to_string_scala(class_name: String, content: Content, scope: Scope) -> String {
+    match content {
+      Content::Object(_) => Template::parse(SCALA_TEMPLATE)
+        .expect("built-in template")
+        .render(&scala_view(
+          class_name,
+          content,
+          scope,
+          &Docs::default(),
+          None,
+          None,
+        )),
+      Content::String | Content::Enum(_) => scope.type_name("string", "String"),
+      Content::Const(value) => {
+        content_to_string_scala(class_name, Content::const_type(&value), scope)
+      }
+      Content::Integer => scala_integer_type(scope),
+      Content::Number => scope.type_name("number", "Float"),
+      Content::Boolean => scope.type_name("boolean", "Boolean"),
+      Content::Date => scope.type_name(
+        "date",
+        if scope.is_command {
+          "ZonedDateTime"
+        } else {
+          "Instant"
+        },
+      ),
+      Content::Binary => scope.type_name(
+        "binary",
+        if scope.is_command {
+          "TemporaryFile"
+        } else {
+          "Array[Byte]"
+        },
+      ),
       Content::Array(content) => format!(
         "Seq[{}]",
-        content_to_string_scala("".to_string(), *content, is_command)
+        content_to_string_scala("".to_string(), *content, scope)
+      ),
+      Content::Union(contents) => contents
+        .into_iter()
+        .map(|content| content_to_string_scala("".to_string(), content, scope))
+        .collect::<Vec<_>>()
+        .join(" | "),
+      Content::Tuple(contents) => format!(
+        "({})",
+        contents
+          .into_iter()
+          .map(|content| content_to_string_scala("".to_string(), content, scope))
+          .collect::<Vec<_>>()
+          .join(", ")
       ),
     }
   }
 
-  fn content_to_string_ts(type_name: String, content: Content) -> String {
-    match content {
-      Content::Object(properties) => {
-        format!(
-          "type {}={{{}}}",
-          type_name,
-          properties
-            .clone()
-            .into_iter()
-            .map(|property| {
-              format!(
-                "{}: {}",
-                property.key,
-                match property.value {
-                  Content::Object(_) => head_uppercase(property.key.to_string()),
-                  _ => content_to_string_ts("".to_string(), property.value),
-                }
-              )
-            })
-            .collect::<Vec<_>>()
-            .join(";\n")
-        ) + "\n"
-          + &properties
-            .into_iter()
-            .filter(|property| match property.value {
-              Content::Object(_) => true,
-              _ => false,
-            })
-            .map(|property| {
-              content_to_string_ts(head_uppercase(property.key.to_string()), property.value)
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
+  fn scala_integer_type(scope: Scope) -> String {
+    scope.type_name("integer", "Int or Long")
+  }
+
+  // Reads validators and ScalaCheck generators must compile, so the `Int or Long` default is Long
+  fn scala_concrete_integer_type(scope: Scope) -> String {
+    scope.type_name("integer", "Long")
+  }
+
+  fn scala_integer_literal(value: i64, integer_type: &str) -> String {
+    if integer_type == "Long" {
+      format!("{}L", value)
+    } else {
+      value.to_string()
+    }
+  }
+
+  // a Scala string literal; unlike Rust's `{:?}` this keeps non-ASCII as is
+  fn scala_string_literal(value: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in value.chars() {
+      match c {
+        '"' => literal.push_str("\\\""),
+        '\\' => literal.push_str("\\\\"),
+        '\n' => literal.push_str("\\n"),
+        '\r' => literal.push_str("\\r"),
+        '\t' => literal.push_str("\\t"),
+        c if c.is_control() => literal.push_str(&format!("\\u{:04x}", c as u32)),
+        c => literal.push(c),
       }
-      Content::String => "string".to_string(),
-      Content::Integer => "number".to_string(),
-      Content::Number => "number".to_string(),
-      Content::Boolean => "boolean".to_string(),
-      Content::Date => "Date".to_string(),
-      Content::Array(content) => content_to_string_ts("".to_string(), *content) + "[]",
     }
+    literal.push('"');
+    literal
   }
 
-  pub fn generate_command_scala(method: Method) -> Option<String> {
-    method
-      .request_body_opt
-      .map(|request_body| content_to_string_scala("Command".to_string(), request_body, true))
+  // play-json Reads checking the constraints, so invalid payloads fail with a JsError
+  fn scala_reads(
+    class_name: &str,
+    properties: &[Property],
+    scala_types: &[String],
+    scope: Scope,
+  ) -> String {
+    let reads = properties
+      .iter()
+      .zip(scala_types.iter())
+      .map(|(property, scala_type)| {
+        let constraints = &property.constraints;
+        let integral = property.value == Content::Integer;
+        let validated_type = if integral {
+          scala_concrete_integer_type(scope)
+        } else {
+          scala_type.clone()
+        };
+        let mut validators = vec![];
+        for length in vec![constraints.min_length, constraints.min_items]
+          .into_iter()
+          .flatten()
+        {
+          validators.push(format!("Reads.minLength[{}]({})", scala_type, length));
+        }
+        for length in vec![constraints.max_length, constraints.max_items]
+          .into_iter()
+          .flatten()
+        {
+          validators.push(format!("Reads.maxLength[{}]({})", scala_type, length));
+        }
+        if let Some(pattern) = &constraints.pattern {
+          // a raw string can't hold `"""`, such patterns are escaped instead
+          let literal = if pattern.contains("\"\"\"") {
+            scala_string_literal(pattern)
+          } else {
+            format!("\"\"\"{}\"\"\"", pattern)
+          };
+          validators.push(format!("Reads.pattern({}.r)", literal));
+        }
+        // integral bounds are rounded inwards, `maximum: 150.5` allows up to 150
+        if let Some(minimum) = constraints.minimum {
+          let bound = if integral {
+            scala_integer_literal(minimum.ceil() as i64, &validated_type)
+          } else {
+            minimum.to_string()
+          };
+          validators.push(format!("Reads.min[{}]({})", validated_type, bound));
+        }
+        if let Some(maximum) = constraints.maximum {
+          let bound = if integral {
+            scala_integer_literal(maximum.floor() as i64, &validated_type)
+          } else {
+            maximum.to_string()
+          };
+          validators.push(format!("Reads.max[{}]({})", validated_type, bound));
+        }
+        if let Some(multiple_of) = constraints.multiple_of {
+          validators.push(format!(
+            "Reads.filter[{0}](JsonValidationError(\"error.multipleOf\", {1}))(_ % {1} == 0)",
+            validated_type, multiple_of
+          ));
+        }
+        if constraints.unique_items {
+          validators.push(format!(
+            "Reads.filter[{}](JsonValidationError(\"error.uniqueItems\"))(s => s.distinct.size == s.size)",
+            scala_type
+          ));
+        }
+        format!(
+          "(__ \\ \"{}\").{}[{}]{}",
+          property.key,
+          if property.or_null {
+            "readNullable"
+          } else {
+            "read"
+          },
+          scala_type,
+          if validators.is_empty() {
+            "".to_string()
+          } else {
+            format!("({})", validators.join(" keepAnd "))
+          }
+        )
+      })
+      .collect::<Vec<_>>();
+    let body = if reads.is_empty() {
+      format!("Reads.pure({}())", class_name)
+    } else if reads.len() == 1 {
+      format!("{}.map({}.apply)", reads[0], class_name)
+    } else {
+      format!(
+        "(\n    {}\n  )({}.apply _)",
+        reads.join(" and\n    "),
+        class_name
+      )
+    };
+    format!("  implicit val reads: Reads[{}] = {}", class_name, body)
   }
 
-  pub fn generate_command_ts(method: Method) -> Option<String> {
-    method
-      .request_body_opt
-      .map(|request_body| content_to_string_ts("Command".to_string(), request_body))
+  fn is_renamed_scala(properties: &[Property], naming: Option<Naming>) -> bool {
+    properties.iter().any(|property| {
+      naming::field_name(&property.key, Target::Scala, naming).trim_matches('`') != property.key
+        || match &property.value {
+          Content::Object(nested) => is_renamed_scala(nested, naming),
+          Content::Array(items) => match &**items {
+            Content::Object(nested) => is_renamed_scala(nested, naming),
+            _ => false,
+          },
+          _ => false,
+        }
+    })
   }
 
-  pub fn generate_view_model_scala(method: Method) -> Option<String> {
-    method
-      .response_opt
-      .map(|response| content_to_string_scala("ViewModel".to_string(), response, false))
+  // play-json Writes putting each field under its JSON key
+  fn scala_writes(
+    class_name: &str,
+    properties: &[Property],
+    scala_types: &[String],
+    names: &[String],
+  ) -> String {
+    let writes = properties
+      .iter()
+      .zip(scala_types.iter())
+      .map(|(property, scala_type)| {
+        format!(
+          "(__ \\ \"{}\").{}[{}]",
+          property.key,
+          if property.or_null {
+            "writeNullable"
+          } else {
+            "write"
+          },
+          scala_type
+        )
+      })
+      .collect::<Vec<_>>();
+    let body = if writes.is_empty() {
+      "OWrites(_ => Json.obj())".to_string()
+    } else if writes.len() == 1 {
+      format!("{}.contramap[{}](_.{})", writes[0], class_name, names[0])
+    } else {
+      format!(
+        "(\n    {}\n  )(unlift({}.unapply))",
+        writes.join(" and\n    "),
+        class_name
+      )
+    };
+    format!("  implicit val writes: OWrites[{}] = {}", class_name, body)
   }
 
-  pub fn generate_view_model_ts(method: Method) -> Option<String> {
-    method
-      .response_opt
-      .map(|response| content_to_string_ts("ViewModel".to_string(), response))
+  /// ScalaCheck instances for the Command and ViewModel case classes, kept in separate objects
+  /// because nested class names may repeat between the two.
+  pub fn generate_arbitrary_scala(method: Method, settings: &TargetSettings) -> Option<String> {
+    let package = package_name(&method.operation_id);
+    let objects = vec![
+      method.request_body_opt.map(|content| {
+        scala_arbitrary_object(
+          "Command".to_string(),
+          content,
+          Scope::command(settings),
+          &format!("{}.command", package),
+        )
+      }),
+      method.response_opt.map(|content| {
+        scala_arbitrary_object(
+          "ViewModel".to_string(),
+          content,
+          Scope::view_model(settings),
+          &format!("{}.viewmodel", package),
+        )
+      }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if objects.is_empty() {
+      None
+    } else {
+      Some(format!(
+        "{}import java.time._\nimport org.scalacheck.{{Arbitrary, Gen}}\nimport play.api.libs.Files.SingletonTemporaryFileCreator\n\n{}",
+        scala_package(settings, &package),
+        objects.join("\n")
+      ))
+    }
   }
 
-  #[cfg(test)]
-  mod tests {
-    use super::*;
-    extern crate yaml_rust;
-    use yaml_rust::YamlLoader;
+  fn scala_arbitrary_object(
+    class_name: String,
+    content: Content,
+    scope: Scope,
+    package: &str,
+  ) -> String {
+    let body = match scala_root_class(&class_name, &content) {
+      Some((root_name, properties)) => {
+        let mut instances = vec![];
+        scala_arbitrary_instances(
+          root_name.clone(),
+          Content::Object(properties.clone()),
+          scope,
+          &mut instances,
+        );
+        if root_name != class_name {
+          instances.push(format!(
+            "  lazy val gen{0}: Gen[Seq[{1}]] = Gen.listOf(Arbitrary.arbitrary[{1}])",
+            class_name, root_name
+          ));
+        }
+        instances.join("\n\n")
+      }
+      None => format!(
+        "  lazy val gen{}: Gen[{}] = {}",
+        class_name,
+        content_to_string_scala("".to_string(), content.clone(), scope),
+        content_to_scala_gen("".to_string(), content, scope)
+      ),
+    };
+    let import = match &scope.settings.package {
+      Some(base) => format!("  import {}.{}._\n\n", base, package),
+      None => "".to_string(),
+    };
+    format!(
+      "object {}Arbitraries {{\n{}{}\n}}\n",
+      class_name, import, body
+    )
+  }
 
-    #[test]
-    fn it_from_yaml() {
-      let yaml = "
-      openapi: 3.0.0
-      info:
-        title: local
-        version: '1.0'
-      servers:
-        - url: 'http://localhost:3000'
-      paths:
-        '/users/{userId}':
-          parameters:
-            - schema:
-                type: string
-              name: userId
-              in: path
-              description: ''
-          get:
-            summary: ユーザ詳細GET
-            tags: []
-            responses:
-              '200':
-                description: OK
-                headers: {}
-                content:
-                  application/json:
-                    schema:
-                      type: object
-                      properties:
-                        hogeId:
-                          type: boolean
-                        foo:
-                          type:
-                            - integer
+  // nested case classes first so every instance is declared before the one using it
+  fn scala_arbitrary_instances(
+    class_name: String,
+    content: Content,
+    scope: Scope,
+    instances: &mut Vec<String>,
+  ) {
+    if let Content::Object(properties) = content {
+      for property in properties.iter() {
+        let nested = match &property.value {
+          Content::Array(items) => *items.clone(),
+          value => value.clone(),
+        };
+        if let Content::Object(_) = nested {
+          scala_arbitrary_instances(
+            to_pascal_case(property.key.clone()),
+            nested,
+            scope,
+            instances,
+          );
+        }
+      }
+      let generators = properties
+        .iter()
+        .map(|property| {
+          let gen = constrained_scala_gen(property, scope);
+          format!(
+            "      {} <- {}",
+            naming::field_name(&property.key, Target::Scala, scope.settings.naming),
+            if property.or_null {
+              format!("Gen.option({})", gen)
+            } else {
+              gen
+            }
+          )
+        })
+        .collect::<Vec<_>>();
+      instances.push(format!(
+        "  implicit lazy val arb{0}: Arbitrary[{0}] = Arbitrary(\n    for {{\n{1}\n    }} yield {0}({2})\n  )",
+        class_name,
+        generators.join("\n"),
+        properties
+          .iter()
+          .map(|property| naming::field_name(&property.key, Target::Scala, scope.settings.naming))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ));
+    }
+  }
+
+  fn constrained_scala_gen(property: &Property, scope: Scope) -> String {
+    let constraints = &property.constraints;
+    let sized = |min: Option<u64>, max: Option<u64>, gen: String| {
+      let min = min.unwrap_or(0);
+      format!(
+        "Gen.choose({}, {}).flatMap(Gen.listOfN(_, {}))",
+        min,
+        max.unwrap_or(min + 32),
+        gen
+      )
+    };
+    match &property.value {
+      Content::String if constraints.min_length.is_some() || constraints.max_length.is_some() => {
+        sized(
+          constraints.min_length,
+          constraints.max_length,
+          "Gen.alphaNumChar".to_string(),
+        ) + ".map(_.mkString)"
+      }
+      Content::Integer if constraints.minimum.is_some() || constraints.maximum.is_some() => {
+        let integer_type = scala_concrete_integer_type(scope);
+        format!(
+          "Gen.chooseNum[{0}]({1}, {2})",
+          integer_type,
+          constraints
+            .minimum
+            .map(|minimum| scala_integer_literal(minimum.ceil() as i64, &integer_type))
+            .unwrap_or_else(|| format!("{}.MinValue", integer_type)),
+          constraints
+            .maximum
+            .map(|maximum| scala_integer_literal(maximum.floor() as i64, &integer_type))
+            .unwrap_or_else(|| format!("{}.MaxValue", integer_type))
+        )
+      }
+      Content::Number if constraints.minimum.is_some() || constraints.maximum.is_some() => format!(
+        "Gen.chooseNum({}, {})",
+        constraints
+          .minimum
+          .map(|minimum| format!("{}f", minimum))
+          .unwrap_or_else(|| "Float.MinValue".to_string()),
+        constraints
+          .maximum
+          .map(|maximum| format!("{}f", maximum))
+          .unwrap_or_else(|| "Float.MaxValue".to_string())
+      ),
+      Content::Array(items)
+        if constraints.min_items.is_some() || constraints.max_items.is_some() =>
+      {
+        sized(
+          constraints.min_items,
+          constraints.max_items,
+          content_to_scala_gen(property.key.clone(), *items.clone(), scope),
+        )
+      }
+      value => content_to_scala_gen(property.key.clone(), value.clone(), scope),
+    }
+  }
+
+  fn content_to_scala_gen(key: String, content: Content, scope: Scope) -> String {
+    match content {
+      Content::Object(_) => format!("Arbitrary.arbitrary[{}]", to_pascal_case(key)),
+      Content::String => "Gen.alphaNumStr".to_string(),
+      Content::Enum(values) => format!(
+        "Gen.oneOf({})",
+        values
+          .iter()
+          .map(|value| scala_string_literal(value))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+      Content::Const(value) => {
+        let scala_type = content_to_string_scala("".to_string(), Content::const_type(&value), scope);
+        let literal = match &value {
+          Yaml::Integer(integer) if scala_type == "Long" => format!("{}L", integer),
+          Yaml::Real(real) if scala_type == "Float" => format!("{}f", real),
+          value => crate::loader::to_json(value),
+        };
+        format!("Gen.const[{}]({})", scala_type, literal)
+      }
+      Content::Integer => format!(
+        "Arbitrary.arbitrary[{}]",
+        scala_concrete_integer_type(scope)
+      ),
+      Content::Number => format!("Arbitrary.arbitrary[{}]", scope.type_name("number", "Float")),
+      Content::Boolean => format!(
+        "Arbitrary.arbitrary[{}]",
+        scope.type_name("boolean", "Boolean")
+      ),
+      Content::Date if scope.is_overridden("date") => {
+        format!("Arbitrary.arbitrary[{}]", scope.type_name("date", ""))
+      }
+      // 1970-01-01 to 2100-01-01
+      Content::Date => if scope.is_command {
+        "Gen.chooseNum(0L, 4102444800L).map(s => ZonedDateTime.ofInstant(Instant.ofEpochSecond(s), ZoneOffset.UTC))"
+      } else {
+        "Gen.chooseNum(0L, 4102444800L).map(Instant.ofEpochSecond)"
+      }
+      .to_string(),
+      Content::Binary => if scope.is_command {
+        "Gen.const(SingletonTemporaryFileCreator.create(\"arbitrary\"))"
+      } else {
+        "Arbitrary.arbitrary[Array[Byte]]"
+      }
+      .to_string(),
+      Content::Array(items) => format!(
+        "Gen.listOf({})",
+        content_to_scala_gen(key, *items, scope)
+      ),
+      Content::Union(contents) => format!(
+        "Gen.oneOf({})",
+        contents
+          .into_iter()
+          .map(|content| content_to_scala_gen(key.clone(), content, scope))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+      Content::Tuple(contents) => format!(
+        "Gen.zip({})",
+        contents
+          .into_iter()
+          .map(|content| content_to_scala_gen(key.clone(), content, scope))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+    }
+  }
+
+  fn has_constraints(content: &Content) -> bool {
+    match content {
+      Content::Object(properties) => properties
+        .iter()
+        .any(|property| !property.constraints.is_empty() || has_constraints(&property.value)),
+      Content::Array(items) => has_constraints(items),
+      Content::Union(contents) | Content::Tuple(contents) => contents.iter().any(has_constraints),
+      Content::Const(_) => true,
+      _ => false,
+    }
+  }
+
+  // types with constraints also get a Zod schema to validate them at runtime
+  fn ts_view(
+    type_name: String,
+    content: Content,
+    scope: Scope,
+    docs: &Docs,
+    with_schema: bool,
+    method: Option<&Method>,
+  ) -> Yaml {
+    let mut classes = vec![];
+    let type_expression = match &content {
+      Content::Object(properties) => {
+        ts_classes(
+          type_name.clone(),
+          properties.clone(),
+          scope,
+          false,
+          &mut classes,
+        );
+        "".to_string()
+      }
+      _ => content_to_string_ts(content.clone(), scope),
+    };
+    let schema = if with_schema && has_constraints(&content) {
+      content_to_zod(&content, &Constraints::default(), scope.settings.naming)
+    } else {
+      "".to_string()
+    };
+    object(vec![
+      ("type_name", Yaml::String(type_name)),
+      ("doc", Yaml::String(doc_comment("", docs.lines()))),
+      ("type", Yaml::String(type_expression)),
+      ("classes", Yaml::Array(classes)),
+      ("schema", Yaml::String(schema)),
+      ("content", content_view(&content)),
+      ("method", method.map(method_view).unwrap_or(Yaml::Null)),
+    ])
+  }
+
+  fn ts_classes(
+    type_name: String,
+    properties: Vec<Property>,
+    scope: Scope,
+    separated: bool,
+    classes: &mut Vec<Yaml>,
+  ) {
+    let names = properties
+      .iter()
+      .map(|property| naming::field_name(&property.key, Target::TypeScript, scope.settings.naming))
+      .collect::<Vec<_>>();
+    // with a naming other than `key`, the fields are mapped to their JSON keys explicitly
+    let keys = if is_renamed_ts(&properties, &names) {
+      format!(
+        "{{{}}}",
+        properties
+          .iter()
+          .zip(names.iter())
+          .map(|(property, name)| format!("{}: {:?}", name, property.key))
+          .collect::<Vec<_>>()
+          .join(", ")
+      )
+    } else {
+      "".to_string()
+    };
+    classes.push(object(vec![
+      ("name", Yaml::String(type_name)),
+      ("separated", Yaml::Boolean(separated)),
+      ("keys", Yaml::String(keys)),
+      (
+        "properties",
+        Yaml::Array(
+          properties
+            .iter()
+            .zip(names.iter())
+            .map(|(property, name)| {
+              let ts_type = match &property.value {
+                Content::Object(_) => to_pascal_case(property.key.to_string()),
+                Content::Array(items) if matches!(**items, Content::Object(_)) => {
+                  format!("{}[]", to_pascal_case(property.key.to_string()))
+                }
+                _ => content_to_string_ts(property.value.clone(), scope),
+              };
+              // matches the `.nullable()` of the Zod schema
+              let nullable_type = if property.or_null {
+                format!("{} | null", ts_type)
+              } else {
+                ts_type.clone()
+              };
+              property_view(
+                property,
+                vec![
+                  ("name", string(name)),
+                  ("type", Yaml::String(nullable_type)),
+                  ("value_type", Yaml::String(ts_type)),
+                ],
+              )
+            })
+            .collect(),
+        ),
+      ),
+    ]));
+    let nested = properties
+      .into_iter()
+      .filter_map(|property| match property.value {
+        Content::Object(nested) => Some((property.key, nested)),
+        Content::Array(items) => match *items {
+          Content::Object(nested) => Some((property.key, nested)),
+          _ => None,
+        },
+        _ => None,
+      });
+    for (index, (key, nested)) in nested.enumerate() {
+      ts_classes(to_pascal_case(key), nested, scope, index > 0, classes);
+    }
+  }
+
+  fn is_renamed_ts(properties: &[Property], names: &[String]) -> bool {
+    properties.iter().zip(names.iter()).any(|(property, name)| {
+      name != &naming::field_name(&property.key, Target::TypeScript, Some(Naming::Key))
+    })
+  }
+
+  // parses the JSON keys and, for a renaming `naming`, transforms to the declared field names
+  fn content_to_zod(
+    content: &Content,
+    constraints: &Constraints,
+    naming: Option<Naming>,
+  ) -> String {
+    let joined = |contents: &Vec<Content>| {
+      contents
+        .iter()
+        .map(|content| content_to_zod(content, &Constraints::default(), naming))
+        .collect::<Vec<_>>()
+        .join(", ")
+    };
+    let mut schema = match content {
+      Content::Object(properties) => {
+        let schema = format!(
+          "z.object({{ {} }})",
+          properties
+            .iter()
+            .map(|property| format!(
+              "{}: {}{}",
+              naming::field_name(&property.key, Target::TypeScript, Some(Naming::Key)),
+              content_to_zod(&property.value, &property.constraints, naming),
+              if property.or_null { ".nullable()" } else { "" }
+            ))
+            .collect::<Vec<_>>()
+            .join(", ")
+        );
+        let names = properties
+          .iter()
+          .map(|property| naming::field_name(&property.key, Target::TypeScript, naming))
+          .collect::<Vec<_>>();
+        if is_renamed_ts(properties, &names) {
+          format!(
+            "{}.transform((value) => ({{ {} }}))",
+            schema,
+            properties
+              .iter()
+              .zip(names.iter())
+              .map(|(property, name)| format!("{}: value[{:?}]", name, property.key))
+              .collect::<Vec<_>>()
+              .join(", ")
+          )
+        } else {
+          schema
+        }
+      }
+      Content::Array(items) => format!(
+        "z.array({})",
+        content_to_zod(items, &Constraints::default(), naming)
+      ),
+      Content::String => "z.string()".to_string(),
+      Content::Enum(values) => format!("z.enum({:?})", values),
+      Content::Const(value) => format!("z.literal({})", crate::loader::to_json(value)),
+      Content::Integer => "z.number().int()".to_string(),
+      Content::Number => "z.number()".to_string(),
+      Content::Boolean => "z.boolean()".to_string(),
+      Content::Date => "z.coerce.date()".to_string(),
+      Content::Binary => "z.instanceof(Blob)".to_string(),
+      Content::Union(contents) => format!("z.union([{}])", joined(contents)),
+      Content::Tuple(contents) => format!("z.tuple([{}])", joined(contents)),
+    };
+    for (method, value) in [
+      ("min", constraints.min_length.or(constraints.min_items)),
+      ("max", constraints.max_length.or(constraints.max_items)),
+    ] {
+      if let Some(value) = value {
+        schema += &format!(".{}({})", method, value);
+      }
+    }
+    if let Some(pattern) = &constraints.pattern {
+      schema += &format!(".regex(/{}/)", pattern.replace('/', "\\/"));
+    }
+    for (method, value) in [
+      ("gte", constraints.minimum),
+      ("lte", constraints.maximum),
+      ("multipleOf", constraints.multiple_of),
+    ] {
+      if let Some(value) = value {
+        schema += &format!(".{}({})", method, value);
+      }
+    }
+    if constraints.unique_items {
+      schema += ".refine((items) => new Set(items).size === items.length, { message: \"items must be unique\" })";
+    }
+    schema
+  }
+
+  // objects without a named class of their own, such as union members, are written inline
+  fn content_to_string_ts(content: Content, scope: Scope) -> String {
+    match content {
+      Content::Object(properties) => format!(
+        "{{{}}}",
+        properties
+          .into_iter()
+          .map(|property| {
+            let ts_type = content_to_string_ts(property.value, scope);
+            format!(
+              "{}: {}",
+              naming::field_name(&property.key, Target::TypeScript, scope.settings.naming),
+              if property.or_null {
+                format!("{} | null", ts_type)
+              } else {
+                ts_type
+              }
+            )
+          })
+          .collect::<Vec<_>>()
+          .join("; ")
+      ),
+      Content::String | Content::Enum(_) => scope.type_name("string", "string"),
+      Content::Const(value) => crate::loader::to_json(&value),
+      Content::Integer => scope.type_name("integer", "number"),
+      Content::Number => scope.type_name("number", "number"),
+      Content::Boolean => scope.type_name("boolean", "boolean"),
+      Content::Date => scope.type_name("date", "Date"),
+      Content::Binary => scope.type_name("binary", "Blob"),
+      Content::Array(content) => content_to_string_ts(*content, scope) + "[]",
+      Content::Union(contents) => contents
+        .into_iter()
+        .map(|content| content_to_string_ts(content, scope))
+        .collect::<Vec<_>>()
+        .join(" | "),
+      Content::Tuple(contents) => format!(
+        "[{}]",
+        contents
+          .into_iter()
+          .map(|content| content_to_string_ts(content, scope))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+    }
+  }
+
+  fn to_pascal_case(str: String) -> String {
+    naming::type_name(&str)
+  }
+
+  fn constraints_comment(indent: &str, marker: &str, constraints: &Constraints) -> String {
+    if constraints.is_empty() {
+      "".to_string()
+    } else {
+      format!("{}{} {}\n", indent, marker, constraints.describe())
+    }
+  }
+
+  fn rust_field_name(key: String, scope: Scope) -> String {
+    naming::field_name(&key, Target::Rust, scope.settings.naming)
+  }
+
+  fn content_to_rust_type(
+    type_name: String,
+    content: Content,
+    scope: Scope,
+  ) -> (String, Vec<String>) {
+    match content {
+      Content::Object(properties) => {
+        let mut definitions = vec![];
+        let fields = properties
+          .into_iter()
+          .map(|property| {
+            let field_name = rust_field_name(property.key.clone(), scope);
+            let comment = constraints_comment("  ", "///", &property.constraints);
+            let (field_type, nested) = content_to_rust_type(
+              type_name.clone() + &to_pascal_case(property.key.clone()),
+              property.value,
+              scope,
+            );
+            definitions.extend(nested);
+            let rename = if field_name.trim_start_matches("r#") != property.key {
+              format!("  #[serde(rename = \"{}\")]\n", property.key)
+            } else {
+              "".to_string()
+            };
+            // optional keys may be missing from the payload, so they default to `None`
+            let default = if property.required {
+              ""
+            } else {
+              "  #[serde(default, skip_serializing_if = \"Option::is_none\")]\n"
+            };
+            format!(
+              "{}{}{}  pub {}: {},",
+              comment,
+              rename,
+              default,
+              field_name,
+              if property.or_null || !property.required {
+                format!("Option<{}>", field_type)
+              } else {
+                field_type
+              }
+            )
+          })
+          .collect::<Vec<_>>()
+          .join("\n");
+        definitions.insert(
+          0,
+          format!(
+            "#[derive(Serialize, Deserialize)]\npub struct {} {{\n{}\n}}\n",
+            type_name, fields
+          ),
+        );
+        (type_name, definitions)
+      }
+      Content::Enum(values) => {
+        let variants = naming::variant_names(&values)
+          .into_iter()
+          .zip(values)
+          .map(|(variant, value)| {
+            if variant != value {
+              format!("  #[serde(rename = \"{}\")]\n  {},", value, variant)
+            } else {
+              format!("  {},", variant)
+            }
+          })
+          .collect::<Vec<_>>()
+          .join("\n");
+        (
+          type_name.clone(),
+          vec![format!(
+            "#[derive(Serialize, Deserialize)]\npub enum {} {{\n{}\n}}\n",
+            type_name, variants
+          )],
+        )
+      }
+      Content::String => (scope.type_name("string", "String"), vec![]),
+      Content::Const(value) => content_to_rust_type(type_name, Content::const_type(&value), scope),
+      Content::Integer => (scope.type_name("integer", "i64"), vec![]),
+      Content::Number => (scope.type_name("number", "f64"), vec![]),
+      Content::Boolean => (scope.type_name("boolean", "bool"), vec![]),
+      Content::Date => (scope.type_name("date", "chrono::NaiveDate"), vec![]),
+      Content::Binary => (scope.type_name("binary", "Vec<u8>"), vec![]),
+      Content::Array(content) => {
+        let (item_type, definitions) = content_to_rust_type(type_name, *content, scope);
+        (format!("Vec<{}>", item_type), definitions)
+      }
+      Content::Union(_) => ("Value".to_string(), vec![]),
+      Content::Tuple(contents) => {
+        let mut definitions = vec![];
+        let item_types = contents
+          .into_iter()
+          .enumerate()
+          .map(|(index, content)| {
+            let (item_type, nested) =
+              content_to_rust_type(format!("{}{}", type_name, index), content, scope);
+            definitions.extend(nested);
+            item_type
+          })
+          .collect::<Vec<_>>();
+        if item_types.len() == 1 {
+          (format!("({},)", item_types[0]), definitions)
+        } else {
+          (format!("({})", item_types.join(", ")), definitions)
+        }
+      }
+    }
+  }
+
+  fn has_union(content: &Content) -> bool {
+    match content {
+      Content::Object(properties) => properties.iter().any(|property| has_union(&property.value)),
+      Content::Array(items) => has_union(items),
+      Content::Union(_) => true,
+      Content::Tuple(contents) => contents.iter().any(has_union),
+      _ => false,
+    }
+  }
+
+  fn content_to_string_rust(type_name: String, content: Content, scope: Scope) -> String {
+    let definitions = match content.clone() {
+      Content::Object(_) | Content::Enum(_) => {
+        content_to_rust_type(type_name, content.clone(), scope).1
+      }
+      _ => {
+        let (alias, definitions) =
+          content_to_rust_type(format!("{}Item", type_name), content.clone(), scope);
+        vec![format!("pub type {} = {};\n", type_name, alias)]
+          .into_iter()
+          .chain(definitions)
+          .collect()
+      }
+    };
+    format!(
+      "use serde::{{Deserialize, Serialize}};\n{}\n{}",
+      // unions are left to serde_json, which has to be a dependency next to serde then
+      if has_union(&content) {
+        "use serde_json::Value;\n"
+      } else {
+        ""
+      },
+      definitions.join("\n")
+    )
+  }
+
+  fn elm_field_name(key: String, scope: Scope) -> String {
+    naming::field_name(&key, Target::Elm, scope.settings.naming)
+  }
+
+  fn elm_parens(expression: String) -> String {
+    if expression.contains(' ') {
+      format!("({})", expression)
+    } else {
+      expression
+    }
+  }
+
+  // (type, decoder, encoder, definitions)
+  fn content_to_elm(
+    type_name: String,
+    content: Content,
+    scope: Scope,
+  ) -> (String, String, String, Vec<String>) {
+    // an overridden type comes with its `<type>Decoder` and `encode<Type>`, like generated ones
+    let primitive =
+      |kind: &str, elm_type: &str, decoder: &str, encoder: &str| match scope.override_for(kind) {
+        Some(custom) => (
+          custom.clone(),
+          format!("{}Decoder", naming::camel_case(custom)),
+          format!("encode{}", naming::pascal_case(custom)),
+          vec![],
+        ),
+        None => (
+          elm_type.to_string(),
+          decoder.to_string(),
+          encoder.to_string(),
+          vec![],
+        ),
+      };
+    match content {
+      Content::Object(properties) => {
+        let lower_name = naming::camel_case(&type_name);
+        let mut definitions = vec![];
+        let mut fields = vec![];
+        let mut decoders = vec![];
+        let mut encoders = vec![];
+        for property in properties {
+          let field_name = elm_field_name(property.key.clone(), scope);
+          let (field_type, decoder, encoder, nested) = content_to_elm(
+            type_name.clone() + &to_pascal_case(property.key.clone()),
+            property.value,
+            scope,
+          );
+          definitions.extend(nested);
+          // optional keys decode to `Nothing` when missing
+          let (field_type, decoder, encoder) = if property.or_null || !property.required {
+            (
+              format!("Maybe {}", elm_parens(field_type)),
+              format!("Decode.nullable {}", elm_parens(decoder)),
+              format!(
+                "Maybe.map {} >> Maybe.withDefault Encode.null",
+                elm_parens(encoder)
+              ),
+            )
+          } else {
+            (field_type, decoder, encoder)
+          };
+          fields.push(format!("{} : {}", field_name, field_type));
+          decoders.push(if property.required {
+            format!(
+              "\n        |> required \"{}\" {}",
+              property.key,
+              elm_parens(decoder)
+            )
+          } else {
+            format!(
+              "\n        |> optional \"{}\" {} Nothing",
+              property.key,
+              elm_parens(decoder)
+            )
+          });
+          encoders.push(format!(
+            "( \"{}\", {} value.{} )",
+            property.key,
+            elm_parens(encoder),
+            field_name
+          ));
+        }
+        let (record, constructor) = if fields.is_empty() {
+          ("{}".to_string(), "{}".to_string())
+        } else {
+          (
+            format!("\n    {{ {}\n    }}", fields.join("\n    , ")),
+            type_name.clone(),
+          )
+        };
+        definitions.insert(
+          0,
+          format!(
+            "type alias {name} ={record}\n\n\n{lower}Decoder : Decoder {name}\n{lower}Decoder =\n    Decode.succeed {constructor}{decoders}\n\n\nencode{name} : {name} -> Encode.Value\nencode{name} value =\n    Encode.object\n        [ {encoders}\n        ]\n",
+            name = type_name,
+            lower = lower_name,
+            record = if fields.is_empty() { format!(" {}", record) } else { record },
+            constructor = constructor,
+            decoders = decoders.join(""),
+            encoders = encoders.join("\n        , "),
+          ),
+        );
+        (
+          type_name.clone(),
+          format!("{}Decoder", lower_name),
+          format!("encode{}", type_name),
+          definitions,
+        )
+      }
+      Content::String | Content::Enum(_) => {
+        primitive("string", "String", "Decode.string", "Encode.string")
+      }
+      Content::Date => primitive("date", "String", "Decode.string", "Encode.string"),
+      Content::Const(value) => content_to_elm(type_name, Content::const_type(&value), scope),
+      Content::Integer => primitive("integer", "Int", "Decode.int", "Encode.int"),
+      Content::Number => primitive("number", "Float", "Decode.float", "Encode.float"),
+      Content::Boolean => primitive("boolean", "Bool", "Decode.bool", "Encode.bool"),
+      Content::Binary => primitive("binary", "Decode.Value", "Decode.value", "identity"),
+      Content::Array(content) => {
+        let (item_type, decoder, encoder, definitions) = content_to_elm(type_name, *content, scope);
+        (
+          format!("List {}", elm_parens(item_type)),
+          format!("Decode.list {}", elm_parens(decoder)),
+          format!("Encode.list {}", elm_parens(encoder)),
+          definitions,
+        )
+      }
+      Content::Union(_) | Content::Tuple(_) => (
+        "Decode.Value".to_string(),
+        "Decode.value".to_string(),
+        "identity".to_string(),
+        vec![],
+      ),
+    }
+  }
+
+  fn content_to_string_elm(type_name: String, content: Content, scope: Scope) -> String {
+    match content {
+      Content::Object(_) => content_to_elm(type_name, content, scope).3.join("\n\n"),
+      _ => {
+        let lower_name = naming::camel_case(&type_name);
+        let (alias, decoder, encoder, definitions) =
+          content_to_elm(format!("{}Item", type_name), content, scope);
+        vec![format!(
+          "type alias {name} =\n    {alias}\n\n\n{lower}Decoder : Decoder {name}\n{lower}Decoder =\n    {decoder}\n\n\nencode{name} : {name} -> Encode.Value\nencode{name} =\n    {encoder}\n",
+          name = type_name,
+          lower = lower_name,
+          alias = alias,
+          decoder = decoder,
+          encoder = encoder,
+        )]
+        .into_iter()
+        .chain(definitions)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+      }
+    }
+  }
+
+  #[derive(PartialEq, Clone, Copy, Debug)]
+  pub enum PythonStyle {
+    Pydantic,
+    Dataclass,
+  }
+
+  /// How Python renders enums: inline `Literal['a', 'b']` or a `class X(str, Enum)`.
+  #[derive(PartialEq, Clone, Copy, Debug)]
+  pub enum PythonEnums {
+    Literal,
+    Enum,
+  }
+
+  /// Quotes like Python's `repr`: single quotes unless the value holds `'` but no `"`.
+  fn python_string_literal(value: &str) -> String {
+    let quote = if value.contains('\'') && !value.contains('"') {
+      '"'
+    } else {
+      '\''
+    };
+    let mut literal = quote.to_string();
+    for c in value.chars() {
+      match c {
+        '\\' => literal.push_str("\\\\"),
+        '\n' => literal.push_str("\\n"),
+        '\r' => literal.push_str("\\r"),
+        '\t' => literal.push_str("\\t"),
+        c if c == quote => {
+          literal.push('\\');
+          literal.push(c);
+        }
+        c if c.is_control() => literal.push_str(&format!("\\x{:02x}", c as u32)),
+        c => literal.push(c),
+      }
+    }
+    literal.push(quote);
+    literal
+  }
+
+  fn python_field_name(key: String, naming: Option<Naming>) -> String {
+    naming::field_name(&key, Target::Python, naming)
+  }
+
+  fn content_to_python(
+    type_name: String,
+    content: Content,
+    style: PythonStyle,
+    enums: PythonEnums,
+    scope: Scope,
+  ) -> (String, Vec<String>) {
+    match content {
+      Content::Object(properties) => {
+        let mut definitions = vec![];
+        let fields = properties
+          .into_iter()
+          .map(|property| {
+            let field_name = python_field_name(property.key.clone(), scope.settings.naming);
+            let comment = constraints_comment("    ", "#", &property.constraints);
+            let (field_type, nested) = content_to_python(
+              type_name.clone() + &to_pascal_case(property.key.clone()),
+              property.value,
+              style,
+              enums,
+              scope,
+            );
+            definitions.extend(nested);
+            let field_type = if property.or_null {
+              format!("Optional[{}]", field_type)
+            } else {
+              field_type
+            };
+            let line = if field_name == property.key {
+              format!("    {}: {}", field_name, field_type)
+            } else {
+              match style {
+                PythonStyle::Pydantic => format!(
+                  "    {}: {} = Field(alias=\"{}\")",
+                  field_name, field_type, property.key
+                ),
+                PythonStyle::Dataclass => format!(
+                  "    {}: {} = field(metadata={{\"alias\": \"{}\"}})",
+                  field_name, field_type, property.key
+                ),
+              }
+            };
+            comment + &line
+          })
+          .collect::<Vec<_>>();
+        definitions.push(format!(
+          "{}class {}{}:\n{}\n",
+          match style {
+            PythonStyle::Pydantic => "",
+            PythonStyle::Dataclass => "@dataclass\n",
+          },
+          type_name,
+          match style {
+            PythonStyle::Pydantic => "(BaseModel)",
+            PythonStyle::Dataclass => "",
+          },
+          if fields.is_empty() {
+            "    pass".to_string()
+          } else {
+            fields.join("\n")
+          }
+        ));
+        (type_name, definitions)
+      }
+      Content::Enum(values) => match enums {
+        PythonEnums::Literal => (
+          format!(
+            "Literal[{}]",
+            values
+              .iter()
+              .map(|value| python_string_literal(value))
+              .collect::<Vec<_>>()
+              .join(", ")
+          ),
+          vec![],
+        ),
+        PythonEnums::Enum => {
+          let members = naming::unique(values.iter().cloned().map(constant_name).collect())
+            .into_iter()
+            .zip(values.iter())
+            .map(|(member, value)| format!("    {} = {}", member, python_string_literal(value)))
+            .collect::<Vec<_>>();
+          (
+            type_name.clone(),
+            vec![format!(
+              "class {}(str, Enum):\n{}\n",
+              type_name,
+              if members.is_empty() {
+                "    pass".to_string()
+              } else {
+                members.join("\n")
+              }
+            )],
+          )
+        }
+      },
+      Content::String => (scope.type_name("string", "str"), vec![]),
+      Content::Const(yaml_rust::Yaml::Integer(value)) => (format!("Literal[{}]", value), vec![]),
+      Content::Const(yaml_rust::Yaml::Boolean(value)) => (
+        format!("Literal[{}]", if value { "True" } else { "False" }),
+        vec![],
+      ),
+      // floats can not be literal types
+      Content::Const(value) => {
+        content_to_python(type_name, Content::const_type(&value), style, enums, scope)
+      }
+      Content::Integer => (scope.type_name("integer", "int"), vec![]),
+      Content::Number => (scope.type_name("number", "float"), vec![]),
+      Content::Boolean => (scope.type_name("boolean", "bool"), vec![]),
+      Content::Date => (scope.type_name("date", "datetime.date"), vec![]),
+      Content::Binary => (scope.type_name("binary", "bytes"), vec![]),
+      Content::Array(content) => {
+        let (item_type, definitions) = content_to_python(type_name, *content, style, enums, scope);
+        (format!("list[{}]", item_type), definitions)
+      }
+      Content::Union(contents) => {
+        let (item_types, definitions) =
+          python_type_arguments(type_name, contents, style, enums, scope);
+        (format!("Union[{}]", item_types.join(", ")), definitions)
+      }
+      Content::Tuple(contents) => {
+        let (item_types, definitions) =
+          python_type_arguments(type_name, contents, style, enums, scope);
+        (format!("tuple[{}]", item_types.join(", ")), definitions)
+      }
+    }
+  }
+
+  fn python_type_arguments(
+    type_name: String,
+    contents: Vec<Content>,
+    style: PythonStyle,
+    enums: PythonEnums,
+    scope: Scope,
+  ) -> (Vec<String>, Vec<String>) {
+    let mut definitions = vec![];
+    let item_types = contents
+      .into_iter()
+      .enumerate()
+      .map(|(index, content)| {
+        let (item_type, nested) = content_to_python(
+          format!("{}{}", type_name, index),
+          content,
+          style,
+          enums,
+          scope,
+        );
+        definitions.extend(nested);
+        item_type
+      })
+      .collect();
+    (item_types, definitions)
+  }
+
+  fn content_to_string_python(
+    type_name: String,
+    content: Content,
+    style: PythonStyle,
+    enums: PythonEnums,
+    scope: Scope,
+  ) -> String {
+    let definitions = match content {
+      Content::Object(_) => content_to_python(type_name, content, style, enums, scope).1,
+      _ => {
+        let (alias, definitions) =
+          content_to_python(format!("{}Item", type_name), content, style, enums, scope);
+        definitions
+          .into_iter()
+          .chain(vec![format!("{} = {}\n", type_name, alias)])
+          .collect()
+      }
+    };
+    let body = definitions.join("\n\n");
+
+    let typing_imports = vec!["Literal", "Optional", "Union"]
+      .into_iter()
+      .filter(|name| body.contains(&format!("{}[", name)))
+      .collect::<Vec<_>>();
+    let standard_imports = vec![
+      if body.contains("datetime.") {
+        Some("import datetime".to_string())
+      } else {
+        None
+      },
+      match style {
+        PythonStyle::Pydantic => None,
+        PythonStyle::Dataclass => Some(if body.contains("field(") {
+          "from dataclasses import dataclass, field".to_string()
+        } else {
+          "from dataclasses import dataclass".to_string()
+        }),
+      },
+      if body.contains("(str, Enum):") {
+        Some("from enum import Enum".to_string())
+      } else {
+        None
+      },
+      if typing_imports.is_empty() {
+        None
+      } else {
+        Some(format!("from typing import {}", typing_imports.join(", ")))
+      },
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    let third_party_imports = match style {
+      PythonStyle::Pydantic => vec![if body.contains("Field(") {
+        "from pydantic import BaseModel, Field".to_string()
+      } else {
+        "from pydantic import BaseModel".to_string()
+      }],
+      PythonStyle::Dataclass => vec![],
+    };
+    let imports = vec![standard_imports, third_party_imports]
+      .into_iter()
+      .filter(|group| !group.is_empty())
+      .map(|group| group.join("\n"))
+      .collect::<Vec<_>>();
+
+    imports.join("\n\n") + "\n\n\n" + &body
+  }
+
+  fn content_to_go(type_name: String, content: Content, scope: Scope) -> (String, Vec<String>) {
+    match content {
+      Content::Object(properties) => {
+        let mut definitions = vec![];
+        let fields = properties
+          .into_iter()
+          .map(|property| {
+            let comment = constraints_comment("\t", "//", &property.constraints);
+            // a nil pointer tells an absent optional field from a zero value, and is sent as
+            // `null` for a required nullable one; slices and interfaces are nil already
+            let is_nilable = matches!(
+              property.value,
+              Content::Array(_) | Content::Binary | Content::Union(_) | Content::Tuple(_)
+            );
+            let (field_type, nested) = content_to_go(
+              type_name.clone() + &to_pascal_case(property.key.clone()),
+              property.value,
+              scope,
+            );
+            definitions.extend(nested);
+            let tag = if property.required {
+              format!("`json:\"{}\"`", property.key)
+            } else {
+              format!("`json:\"{},omitempty\"`", property.key)
+            };
+            (
+              naming::field_name(&property.key, Target::Go, scope.settings.naming),
+              if property.or_null || (!property.required && !is_nilable) {
+                format!("*{}", field_type)
+              } else {
+                field_type
+              },
+              tag,
+              comment,
+            )
+          })
+          .collect::<Vec<_>>();
+        // gofmt aligns by characters, not bytes
+        let width = |text: &String| text.chars().count();
+        let name_width = fields
+          .iter()
+          .map(|field| width(&field.0))
+          .max()
+          .unwrap_or(0);
+        let type_width = fields
+          .iter()
+          .map(|field| width(&field.1))
+          .max()
+          .unwrap_or(0);
+        definitions.insert(
+          0,
+          format!(
+            "type {} struct {{\n{}}}\n",
+            type_name,
+            fields
+              .into_iter()
+              .map(|(name, field_type, tag, comment)| format!(
+                "{}\t{:name_width$} {:type_width$} {}\n",
+                comment,
+                name,
+                field_type,
+                tag,
+                name_width = name_width,
+                type_width = type_width
+              ))
+              .collect::<String>()
+          ),
+        );
+        (type_name, definitions)
+      }
+      Content::Enum(values) => {
+        let constants = values
+          .into_iter()
+          .map(|value| {
+            (
+              format!("{}{}", type_name, to_pascal_case(value.clone())),
+              value,
+            )
+          })
+          .collect::<Vec<_>>();
+        let name_width = constants
+          .iter()
+          .map(|constant| constant.0.chars().count())
+          .max()
+          .unwrap_or(0);
+        (
+          type_name.clone(),
+          vec![format!(
+            "type {name} string\n\nconst (\n{constants})\n",
+            name = type_name,
+            constants = constants
+              .into_iter()
+              .map(|(constant, value)| format!(
+                "\t{:width$} {} = \"{}\"\n",
+                constant,
+                type_name,
+                value,
+                width = name_width
+              ))
+              .collect::<String>()
+          )],
+        )
+      }
+      Content::String => (scope.type_name("string", "string"), vec![]),
+      Content::Const(value) => content_to_go(type_name, Content::const_type(&value), scope),
+      Content::Integer => (scope.type_name("integer", "int64"), vec![]),
+      Content::Number => (scope.type_name("number", "float64"), vec![]),
+      Content::Boolean => (scope.type_name("boolean", "bool"), vec![]),
+      Content::Date => (scope.type_name("date", "time.Time"), vec![]),
+      Content::Binary => (scope.type_name("binary", "[]byte"), vec![]),
+      Content::Array(content) => {
+        let (item_type, definitions) = content_to_go(type_name, *content, scope);
+        (format!("[]{}", item_type), definitions)
+      }
+      Content::Union(_) => ("interface{}".to_string(), vec![]),
+      Content::Tuple(_) => ("[]interface{}".to_string(), vec![]),
+    }
+  }
+
+  fn content_to_string_go(
+    package: String,
+    type_name: String,
+    content: Content,
+    scope: Scope,
+  ) -> String {
+    let definitions = match content {
+      Content::Object(_) | Content::Enum(_) => content_to_go(type_name, content, scope).1,
+      _ => {
+        let (alias, definitions) = content_to_go(format!("{}Item", type_name), content, scope);
+        vec![format!("type {} {}\n", type_name, alias)]
+          .into_iter()
+          .chain(definitions)
+          .collect()
+      }
+    };
+    let body = definitions.join("\n");
+
+    format!(
+      "package {}\n\n{}{}",
+      package,
+      if body.contains("time.Time") {
+        "import \"time\"\n\n"
+      } else {
+        ""
+      },
+      body
+    )
+  }
+
+  #[derive(PartialEq, Clone, Copy, Debug)]
+  pub enum JavaStyle {
+    Record,
+    Pojo,
+  }
+
+  pub fn package_name(operation_id: &str) -> String {
+    naming::escape(&naming::snake_case(operation_id), Target::Java)
+  }
+
+  fn java_field_name(key: String, scope: Scope) -> String {
+    naming::field_name(&key, Target::Java, scope.settings.naming)
+  }
+
+  fn java_indent(str: String) -> String {
+    str
+      .lines()
+      .map(|line| {
+        if line.is_empty() {
+          line.to_string()
+        } else {
+          format!("  {}", line)
+        }
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+
+  fn java_class_body(definitions: Vec<String>, members: Vec<String>) -> String {
+    members
+      .into_iter()
+      .chain(definitions.into_iter().map(java_indent))
+      .collect::<Vec<_>>()
+      .join("\n\n")
+  }
+
+  /// UPPER_SNAKE_CASE enum constant, prefixed with `V_` unless it starts with a letter.
+  fn constant_name(value: String) -> String {
+    let mut constant = String::new();
+    let mut previous: Option<char> = None;
+    for c in value.chars() {
+      if !c.is_alphanumeric() {
+        constant.push('_');
+      } else {
+        if c.is_uppercase() && previous.map(|p| p.is_lowercase() || p.is_numeric()) == Some(true) {
+          constant.push('_');
+        }
+        constant.extend(c.to_uppercase());
+      }
+      previous = Some(c);
+    }
+    match constant.chars().next() {
+      Some(head) if head.is_alphabetic() => constant,
+      _ => format!("V_{}", constant),
+    }
+  }
+
+  fn java_enum_definition(type_name: String, values: Vec<String>) -> String {
+    format!(
+      "public enum {} {{\n{}\n}}",
+      type_name,
+      naming::unique(values.iter().cloned().map(constant_name).collect())
+        .into_iter()
+        .zip(values)
+        .map(|(constant, value)| format!("  @JsonProperty(\"{}\")\n  {}", value, constant))
+        .collect::<Vec<_>>()
+        .join(",\n")
+    )
+  }
+
+  fn java_accessors(field_type: String, field_name: String) -> String {
+    let upper_name = head_uppercase(field_name.trim_end_matches('_').to_string());
+    format!(
+      "  public {field_type} get{upper}() {{\n    return {name};\n  }}\n\n  public void set{upper}({field_type} {name}) {{\n    this.{name} = {name};\n  }}",
+      field_type = field_type,
+      upper = upper_name,
+      name = field_name
+    )
+  }
+
+  fn content_to_java_type(
+    type_name: String,
+    content: Content,
+    style: JavaStyle,
+    scope: Scope,
+  ) -> (String, Vec<String>) {
+    match content {
+      Content::Object(properties) => (
+        type_name.clone(),
+        vec![java_object_definition(
+          type_name, properties, style, scope, true,
+        )],
+      ),
+      Content::Enum(values) => (
+        type_name.clone(),
+        vec![java_enum_definition(type_name, values)],
+      ),
+      Content::String => (scope.type_name("string", "String"), vec![]),
+      Content::Const(value) => {
+        content_to_java_type(type_name, Content::const_type(&value), style, scope)
+      }
+      Content::Integer => (scope.type_name("integer", "Long"), vec![]),
+      Content::Number => (scope.type_name("number", "Double"), vec![]),
+      Content::Boolean => (scope.type_name("boolean", "Boolean"), vec![]),
+      Content::Date => (scope.type_name("date", "LocalDate"), vec![]),
+      Content::Binary => (scope.type_name("binary", "byte[]"), vec![]),
+      Content::Array(content) => {
+        let (item_type, definitions) = content_to_java_type(type_name, *content, style, scope);
+        (format!("List<{}>", item_type), definitions)
+      }
+      Content::Union(_) => ("Object".to_string(), vec![]),
+      Content::Tuple(_) => ("List<Object>".to_string(), vec![]),
+    }
+  }
+
+  fn java_object_definition(
+    type_name: String,
+    properties: Vec<Property>,
+    style: JavaStyle,
+    scope: Scope,
+    is_nested: bool,
+  ) -> String {
+    let mut definitions = vec![];
+    let fields = properties
+      .into_iter()
+      .map(|property| {
+        let (field_type, nested) = content_to_java_type(
+          type_name.clone() + &to_pascal_case(property.key.clone()),
+          property.value,
+          style,
+          scope,
+        );
+        definitions.extend(nested);
+        (
+          property.key.clone(),
+          java_field_name(property.key, scope),
+          field_type,
+          property.or_null,
+          property.constraints,
+        )
+      })
+      .collect::<Vec<_>>();
+
+    match style {
+      JavaStyle::Record => {
+        let components = fields
+          .into_iter()
+          .map(|(key, name, field_type, or_null, constraints)| {
+            format!(
+              "{}    @JsonProperty(\"{}\") {} {}",
+              constraints_comment("    ", "//", &constraints),
+              key,
+              if or_null {
+                format!("Optional<{}>", field_type)
+              } else {
+                field_type
+              },
+              name
+            )
+          })
+          .collect::<Vec<_>>()
+          .join(",\n");
+        format!(
+          "public record {}(\n{}) {{\n{}}}",
+          type_name,
+          components,
+          if definitions.is_empty() {
+            "".to_string()
+          } else {
+            java_class_body(definitions, vec![]) + "\n"
+          }
+        )
+      }
+      JavaStyle::Pojo => {
+        let declarations = fields
+          .iter()
+          .map(|(key, name, field_type, or_null, constraints)| {
+            format!(
+              "{}{}  @JsonProperty(\"{}\")\n  private {} {};",
+              constraints_comment("  ", "//", constraints),
+              if *or_null { "  @Nullable\n" } else { "" },
+              key,
+              field_type,
+              name
+            )
+          })
+          .collect::<Vec<_>>()
+          .join("\n");
+        let accessors = fields
+          .into_iter()
+          .map(|(_, name, field_type, _, _)| java_accessors(field_type, name))
+          .collect::<Vec<_>>();
+        format!(
+          "public {}class {} {{\n{}\n}}",
+          if is_nested { "static " } else { "" },
+          type_name,
+          java_class_body(
+            definitions,
+            vec![declarations].into_iter().chain(accessors).collect()
+          )
+        )
+      }
+    }
+  }
+
+  fn content_to_string_java(
+    package: String,
+    type_name: String,
+    content: Content,
+    style: JavaStyle,
+    scope: Scope,
+  ) -> String {
+    let definition = match content {
+      Content::Object(properties) => {
+        java_object_definition(type_name, properties, style, scope, false)
+      }
+      Content::Enum(values) => java_enum_definition(type_name, values),
+      _ => {
+        let (value_type, definitions) =
+          content_to_java_type(format!("{}Item", type_name), content, style, scope);
+        match style {
+          JavaStyle::Record => format!(
+            "public record {name}(@JsonValue {value_type} value) {{\n{body}\n}}",
+            name = type_name,
+            value_type = value_type,
+            body = java_class_body(
+              definitions,
+              vec![format!(
+                "  @JsonCreator(mode = JsonCreator.Mode.DELEGATING)\n  public {} {{\n  }}",
+                type_name
+              )]
+            )
+          ),
+          JavaStyle::Pojo => format!(
+            "public class {name} {{\n{body}\n}}",
+            name = type_name,
+            body = java_class_body(
+              definitions,
+              vec![
+                format!("  private final {} value;", value_type),
+                format!(
+                  "  @JsonCreator(mode = JsonCreator.Mode.DELEGATING)\n  public {}({} value) {{\n    this.value = value;\n  }}",
+                  type_name, value_type
+                ),
+                format!(
+                  "  @JsonValue\n  public {} getValue() {{\n    return value;\n  }}",
+                  value_type
+                ),
+              ]
+            )
+          ),
+        }
+      }
+    };
+
+    let imports = vec![
+      (
+        "@JsonCreator",
+        "com.fasterxml.jackson.annotation.JsonCreator",
+      ),
+      (
+        "@JsonProperty",
+        "com.fasterxml.jackson.annotation.JsonProperty",
+      ),
+      ("@JsonValue", "com.fasterxml.jackson.annotation.JsonValue"),
+      ("LocalDate", "java.time.LocalDate"),
+      ("List<", "java.util.List"),
+      ("Optional<", "java.util.Optional"),
+      ("@Nullable", "javax.annotation.Nullable"),
+    ]
+    .into_iter()
+    .filter(|(usage, _)| definition.contains(usage))
+    .map(|(_, import)| format!("import {};", import))
+    .collect::<Vec<_>>();
+
+    format!(
+      "package {};\n\n{}{}\n",
+      package,
+      if imports.is_empty() {
+        "".to_string()
+      } else {
+        imports.join("\n") + "\n\n"
+      },
+      definition
+    )
+  }
+
+  pub fn generate_command_scala(method: Method, settings: &TargetSettings) -> Option<String> {
+    let package = configured_package(
+      settings,
+      &format!("{}.command", package_name(&method.operation_id)),
+    );
+    method.request_body_opt.clone().map(|request_body| {
+      let view = scala_view(
+        "Command".to_string(),
+        request_body,
+        Scope::command(settings),
+        &method.docs,
+        package,
+        Some(&method),
+      );
+      render(settings, "command", SCALA_TEMPLATE, &view)
+    })
+  }
+
+  /// `package` is appended to the configured base package, e.g. `errors`.
+  pub fn generate_response_scala(
+    type_name: String,
+    content: Content,
+    settings: &TargetSettings,
+    package: &str,
+  ) -> String {
+    let view = scala_view(
+      type_name,
+      content,
+      Scope::view_model(settings),
+      &Docs::default(),
+      configured_package(settings, package),
+      None,
+    );
+    render(settings, "response", SCALA_TEMPLATE, &view)
+  }
+
+  pub fn generate_response_ts(
+    type_name: String,
+    content: Content,
+    settings: &TargetSettings,
+  ) -> String {
+    let view = ts_view(
+      type_name,
+      content,
+      Scope::view_model(settings),
+      &Docs::default(),
+      true,
+      None,
+    );
+    render(settings, "response", TYPESCRIPT_TEMPLATE, &view)
+  }
+
+  /// Every response of an operation as a union tagged by status, for clients. Bodies refer to
+  /// the ViewModels and `error_types`, imported from their files next to it and under `errors`,
+  /// ranges and `default` match any status number.
+  pub fn generate_result_ts(method: Method, error_types: &[(String, Content)]) -> Option<String> {
+    if method.response_map.is_empty() {
+      return None;
+    }
+    let primary_status = primary_success_status(&method.response_map);
+    let errors = operation_errors(&method, error_types);
+    let mut imports: Vec<String> = vec![];
+    let members = method
+      .response_map
+      .iter()
+      .map(|(status, body)| {
+        let type_name = if !is_success_status(status) {
+          errors
+            .iter()
+            .find(|(error_status, _)| error_status == status)
+            .and_then(|(_, type_name)| type_name.clone())
+        } else if Some(status) == primary_status.as_ref() {
+          Some("ViewModel".to_string())
+        } else {
+          Some(format!("ViewModel{}", status))
+        };
+        let status_type = if status.parse::<u16>().is_ok() {
+          status.clone()
+        } else {
+          "number".to_string()
+        };
+        match (body, type_name) {
+          (Some(_), Some(type_name)) => {
+            let module = if !is_success_status(status) {
+              format!("../../errors/{}", type_name)
+            } else if type_name == "ViewModel" {
+              format!("./{}", method.operation_id)
+            } else {
+              format!("./{}-{}", method.operation_id, type_name)
+            };
+            let import = format!("import type {{ {} }} from {:?};\n", type_name, module);
+            if !imports.contains(&import) {
+              imports.push(import);
+            }
+            format!("  | {{status: {}; body: {}}}\n", status_type, type_name)
+          }
+          _ => format!("  | {{status: {}}}\n", status_type),
+        }
+      })
+      .collect::<String>();
+    Some(format!(
+      "{}{}export type Result=\n{}",
+      if imports.is_empty() {
+        "".to_string()
+      } else {
+        imports.concat() + "\n"
+      },
+      doc_comment("", vec![format!("{} responses", method.summary)]),
+      members
+    ))
+  }
+
+  pub fn generate_command_ts(method: Method, settings: &TargetSettings) -> Option<String> {
+    method.request_body_opt.clone().map(|request_body| {
+      let view = ts_view(
+        "Command".to_string(),
+        request_body,
+        Scope::command(settings),
+        &method.docs,
+        true,
+        Some(&method),
+      );
+      render(settings, "command", TYPESCRIPT_TEMPLATE, &view)
+    })
+  }
+
+  pub fn generate_view_model_scala(method: Method, settings: &TargetSettings) -> Option<String> {
+    let package = configured_package(
+      settings,
+      &format!("{}.viewmodel", package_name(&method.operation_id)),
+    );
+    method.response_opt.clone().map(|response| {
+      let view = scala_view(
+        "ViewModel".to_string(),
+        response,
+        Scope::view_model(settings),
+        &method.docs,
+        package,
+        Some(&method),
+      );
+      render(settings, "viewmodel", SCALA_TEMPLATE, &view)
+    })
+  }
+
+  pub fn generate_view_model_ts(method: Method, settings: &TargetSettings) -> Option<String> {
+    method.response_opt.clone().map(|response| {
+      let view = ts_view(
+        "ViewModel".to_string(),
+        response,
+        Scope::view_model(settings),
+        &method.docs,
+        true,
+        Some(&method),
+      );
+      render(settings, "viewmodel", TYPESCRIPT_TEMPLATE, &view)
+    })
+  }
+
+  pub fn generate_command_rust(method: Method, settings: &TargetSettings) -> Option<String> {
+    method.request_body_opt.map(|request_body| {
+      content_to_string_rust(
+        "Command".to_string(),
+        request_body,
+        Scope::command(settings),
+      )
+    })
+  }
+
+  pub fn generate_view_model_rust(method: Method, settings: &TargetSettings) -> Option<String> {
+    method.response_opt.map(|response| {
+      content_to_string_rust(
+        "ViewModel".to_string(),
+        response,
+        Scope::view_model(settings),
+      )
+    })
+  }
+
+  pub fn elm_module_name(method: Method) -> String {
+    to_pascal_case(method.operation_id)
+  }
+
+  pub fn generate_elm(method: Method, settings: &TargetSettings) -> Option<String> {
+    let definitions = method
+      .request_body_opt
+      .clone()
+      .map(|request_body| {
+        content_to_string_elm(
+          "Command".to_string(),
+          request_body,
+          Scope::command(settings),
+        )
+      })
+      .into_iter()
+      .chain(method.response_opt.clone().map(|response| {
+        content_to_string_elm(
+          "ViewModel".to_string(),
+          response,
+          Scope::view_model(settings),
+        )
+      }))
+      .collect::<Vec<_>>();
+
+    if definitions.is_empty() {
+      None
+    } else {
+      Some(
+        format!(
+          "module {} exposing (..)\n\nimport Json.Decode as Decode exposing (Decoder)\nimport Json.Decode.Pipeline exposing (optional, required)\nimport Json.Encode as Encode\n\n\n",
+          elm_module_name(method)
+        ) + &definitions.join("\n\n"),
+      )
+    }
+  }
+
+  pub fn python_module_name(method: Method) -> String {
+    python_field_name(method.operation_id, None)
+  }
+
+  pub fn generate_command_python(
+    method: Method,
+    style: PythonStyle,
+    enums: PythonEnums,
+    settings: &TargetSettings,
+  ) -> Option<String> {
+    method.request_body_opt.map(|request_body| {
+      content_to_string_python(
+        "Command".to_string(),
+        request_body,
+        style,
+        enums,
+        Scope::command(settings),
+      )
+    })
+  }
+
+  pub fn generate_view_model_python(
+    method: Method,
+    style: PythonStyle,
+    enums: PythonEnums,
+    settings: &TargetSettings,
+  ) -> Option<String> {
+    method.response_opt.map(|response| {
+      content_to_string_python(
+        "ViewModel".to_string(),
+        response,
+        style,
+        enums,
+        Scope::view_model(settings),
+      )
+    })
+  }
+
+  pub fn generate_command_go(method: Method, settings: &TargetSettings) -> Option<String> {
+    method.request_body_opt.map(|request_body| {
+      content_to_string_go(
+        "command".to_string(),
+        "Command".to_string(),
+        request_body,
+        Scope::command(settings),
+      )
+    })
+  }
+
+  pub fn generate_view_model_go(method: Method, settings: &TargetSettings) -> Option<String> {
+    method.response_opt.map(|response| {
+      content_to_string_go(
+        "viewmodel".to_string(),
+        "ViewModel".to_string(),
+        response,
+        Scope::view_model(settings),
+      )
+    })
+  }
+
+  pub fn generate_command_java(
+    method: Method,
+    style: JavaStyle,
+    settings: &TargetSettings,
+  ) -> Option<String> {
+    let package = format!("{}.command", package_name(&method.operation_id));
+    let package = configured_package(settings, &package).unwrap_or(package);
+    method.request_body_opt.map(|request_body| {
+      content_to_string_java(
+        package,
+        "Command".to_string(),
+        request_body,
+        style,
+        Scope::command(settings),
+      )
+    })
+  }
+
+  pub fn generate_view_model_java(
+    method: Method,
+    style: JavaStyle,
+    settings: &TargetSettings,
+  ) -> Option<String> {
+    let package = format!("{}.viewmodel", package_name(&method.operation_id));
+    let package = configured_package(settings, &package).unwrap_or(package);
+    method.response_opt.map(|response| {
+      content_to_string_java(
+        package,
+        "ViewModel".to_string(),
+        response,
+        style,
+        Scope::view_model(settings),
+      )
+    })
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+    extern crate yaml_rust;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn it_from_yaml() {
+      let yaml = "
+      openapi: 3.0.0
+      info:
+        title: local
+        version: '1.0'
+      servers:
+        - url: 'http://localhost:3000'
+      paths:
+        '/users/{userId}':
+          parameters:
+            - schema:
+                type: string
+              name: userId
+              in: path
+              description: ''
+          get:
+            summary: ユーザ詳細GET
+            tags: []
+            responses:
+              '200':
+                description: OK
+                headers: {}
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        hogeId:
+                          type: boolean
+                        foo:
+                          type:
+                            - integer
                             - 'null'
                         bar_at:
                           type: string
-                          format: date
-                        strList:
+                          format: date
+                        strList:
+                          type: array
+                          items:
+                            type: string
+            operationId: get-users-userId
+            description: ユーザ詳細GET
+          put:
+            summary: ユーザ詳細PUT
+            operationId: put-users-userId
+            responses:
+              '200':
+                description: OK
+            requestBody:
+              content:
+                application/json:
+                  schema:
+                    type: object
+                    properties:
+                      hasDateAndPlace:
+                        type: string
+                      location:
+                        type: string
+                        enum:
+                          - S
+                          - A
+                          - B
+                          - NG
+                      timeMaybe:
+                        type: 
+                          - 'null'
+                          - object
+                        properties:
+                          hour:
+                            type: integer
+                          minute:
+                            type: integer
+            description: ユーザ詳細PUT
+        /users:
+          get:
+            summary: ユーザ取得
+            tags: []
+            responses:
+              '200':
+                description: OK
+                content:
+                  application/json:
+                    schema:
+                      type: array
+                      items:
+                        type: object
+                        properties:
+                          userId:
+                            type: string
+                          age:
+                            type: integer
+                          family:
+                            type: object
+                            properties:
+                              name:
+                                type: string
+                              age:
+                                type: integer
+                          objList:
+                            type: array
+                            items:
+                              type: object
+                              properties:
+                                hoge:
+                                  type: string
+            operationId: get-users
+            description: ユーザ取得
+      operationId: get-users
+      description: ユーザ取得
+      components:
+        schemas: {}                 
+            ";
+
+      let docs = YamlLoader::load_from_str(yaml).unwrap();
+
+      // Multi document support, doc is a yaml::Yaml
+      let doc = &docs[0];
+
+      let user_detail = Content::Object(vec![
+        Property::new("hogeId", Content::Boolean),
+        Property {
+          or_null: true,
+          ..Property::new("foo", Content::Integer)
+        },
+        Property::new("bar_at", Content::Date),
+        Property::new("strList", Content::Array(Box::new(Content::String))),
+      ]);
+      let users = Content::Array(Box::new(Content::Object(vec![
+        Property::new("userId", Content::String),
+        Property::new("age", Content::Integer),
+        Property::new(
+          "family",
+          Content::Object(vec![
+            Property::new("name", Content::String),
+            Property::new("age", Content::Integer),
+          ]),
+        ),
+        Property::new(
+          "objList",
+          Content::Array(Box::new(Content::Object(vec![Property::new(
+            "hoge",
+            Content::String,
+          )]))),
+        ),
+      ])));
+
+      let vec: Vec<Api> = vec![
+        Api {
+          path: "/users/{userId}".to_string(),
+          param_map: linked_hash_map! {"userId".to_string() => ParamType::String},
+          method_map: linked_hash_map! {
+            "get".to_string() => Method{
+              operation_id: "get-users-userId".to_string(),
+              summary: "ユーザ詳細GET".to_string(),
+              response_opt: Some(user_detail.clone()),
+             request_body_opt: None,
+             request_media_type: None,
+             docs: Docs {
+               description: Some("ユーザ詳細GET".to_string()),
+               ..Docs::default()
+             },
+             response_map: linked_hash_map! {"200".to_string() => Some(Body{media_type: "application/json".to_string(), content: user_detail})},
+             },
+            "put".to_string() => Method{
+              operation_id: "put-users-userId".to_string(),
+              summary: "ユーザ詳細PUT".to_string(),
+              response_opt:  None,
+              request_body_opt:  Some(Content::Object(vec![
+                Property::new("hasDateAndPlace", Content::String),
+                Property::new("location", Content::Enum(vec!["S".to_string(), "A".to_string(), "B".to_string(), "NG".to_string()])),
+                Property { or_null: true, ..Property::new("timeMaybe", Content::Object(vec![
+                    Property::new("hour", Content::Integer),
+                    Property::new("minute", Content::Integer)
+                ])) },
+              ])),
+              request_media_type: Some("application/json".to_string()),
+              docs: Docs {
+                description: Some("ユーザ詳細PUT".to_string()),
+                ..Docs::default()
+              },
+              response_map: linked_hash_map! {"200".to_string() => None},
+            },
+          },
+        },
+        Api {
+          path: "/users".to_string(),
+          param_map: LinkedHashMap::new(),
+          method_map: linked_hash_map! {
+            "get".to_string() => Method{
+              operation_id: "get-users".to_string(),
+              summary: "ユーザ取得".to_string(),
+              response_opt: Some(users.clone()),
+             request_body_opt: None,
+             request_media_type: None,
+             docs: Docs {
+               description: Some("ユーザ取得".to_string()),
+               ..Docs::default()
+             },
+             response_map: linked_hash_map! {"200".to_string() => Some(Body{media_type: "application/json".to_string(), content: users})},
+             },
+          },
+        },
+      ];
+
+      assert_eq!(vec, from_yaml(doc));
+    }
+
+    #[test]
+    fn it_from_yaml_keeps_spec_order() {
+      let yaml = "
+      openapi: 3.0.0
+      paths:
+        /orgs/{orgId}/users/{userId}:
+          parameters:
+            - name: orgId
+              in: path
+              schema:
+                type: string
+            - name: userId
+              in: path
+              schema:
+                type: integer
+          put:
+            summary: ユーザ詳細PUT
+            operationId: put-users-userId
+          get:
+            summary: ユーザ詳細GET
+            operationId: get-users-userId
+      ";
+      let api = from_yaml(&YamlLoader::load_from_str(yaml).unwrap()[0])[0].clone();
+
+      assert_eq!(
+        vec!["put", "get"],
+        api.method_map.keys().collect::<Vec<_>>()
+      );
+      assert_eq!(
+        vec![
+          "PUT /orgs/:orgId/users/:userId {Method Name}(orgId: String, userId: Long)",
+          "GET /orgs/:orgId/users/:userId {Method Name}(orgId: String, userId: Long)",
+        ],
+        to_play_routings(api, &[])
+      );
+    }
+
+    #[test]
+    fn it_from_yaml_without_summary() {
+      let yaml = "
+      openapi: 3.0.0
+      paths:
+        /users:
+          get:
+            operationId: get-users
+          post:
+            summary: ' '
+            operationId: post-users
+      ";
+      let api = from_yaml(&YamlLoader::load_from_str(yaml).unwrap()[0])[0].clone();
+
+      assert_eq!("get-users", api.method_map["get"].summary);
+      assert_eq!("post-users", api.method_map["post"].summary);
+    }
+
+    #[test]
+    fn it_from_yaml_responses() {
+      let yaml = "
+      openapi: 3.0.0
+      paths:
+        /users:
+          post:
+            summary: ユーザ作成
+            operationId: post-users
+            responses:
+              201:
+                content:
+                  application/json:
+                    schema:
+                      type: integer
+              '202':
+                content:
+                  application/json:
+                    schema:
+                      type: string
+              '404':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        message:
+                          type: string
+              default:
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        message:
+                          type: string
+          get:
+            summary: ユーザ取得
+            operationId: get-users
+            responses:
+              '200':
+                content:
+                  application/json:
+                    schema:
+                      type: boolean
+              '4XX':
+                content:
+                  application/json:
+                    schema:
+                      type: string
+        /users/{userId}:
+          parameters:
+            - name: userId
+              in: path
+              schema:
+                type: string
+          delete:
+            summary: ユーザ削除
+            operationId: delete-users-userId
+            responses:
+              '204':
+                description: No Content
+              '404':
+                description: Not Found
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        message:
+                          type: string
+              '409':
+                description: Conflict
+      ";
+      let doc = &YamlLoader::load_from_str(yaml).unwrap()[0];
+      let apis = from_yaml(doc);
+      let post = apis[0].method_map["post"].clone();
+      let error = Content::Object(vec![Property::new("message", Content::String)]);
+
+      assert_eq!(Some(Content::Integer), post.response_opt);
+      assert_eq!(
+        Some(&error),
+        post.response_map["default"]
+          .as_ref()
+          .map(|body| &body.content)
+      );
+      assert_eq!(
+        vec![("ViewModel202".to_string(), Content::String)],
+        additional_view_models(post.clone())
+      );
+      assert_eq!(
+        Some(Content::Boolean),
+        apis[0].method_map["get"].response_opt
+      );
+      assert_eq!(
+        vec![
+          ("ErrorResponse4XX".to_string(), Content::String),
+          ("ErrorResponse404".to_string(), error),
+        ],
+        error_types(apis.clone())
+      );
+
+      let delete = apis[1].method_map["delete"].clone();
+      let errors = error_types(apis.clone());
+      assert_eq!(None, delete.response_opt);
+      assert_eq!(Some(&None), delete.response_map.get("409"));
+      assert_eq!(
+        Some("204".to_string()),
+        primary_success_status(&delete.response_map)
+      );
+      assert_eq!(
+        vec![
+          ("404".to_string(), Some("ErrorResponse404".to_string())),
+          ("409".to_string(), None),
+        ],
+        operation_errors(&delete, &errors)
+      );
+      assert_eq!(
+        vec!["# errors: 404 ErrorResponse404, 409\nDELETE /users/:userId {Method Name}(userId: String)"],
+        to_play_routings(apis[1].clone(), &errors)
+      );
+      assert_eq!(
+        Some(
+          "import type { ErrorResponse404 } from \"../../errors/ErrorResponse404\";\n\n"
+            .to_string()
+            + "/** ユーザ削除 responses */\nexport type Result=\n"
+            + "  | {status: 204}\n"
+            + "  | {status: 404; body: ErrorResponse404}\n"
+            + "  | {status: 409}\n"
+        ),
+        generate_result_ts(delete, &errors)
+      );
+      assert_eq!(
+        Some(
+          "import type { ViewModel } from \"./post-users\";\n".to_string()
+            + "import type { ViewModel202 } from \"./post-users-ViewModel202\";\n"
+            + "import type { ErrorResponse404 } from \"../../errors/ErrorResponse404\";\n\n"
+            + "/** ユーザ作成 responses */\nexport type Result=\n"
+            + "  | {status: 201; body: ViewModel}\n"
+            + "  | {status: 202; body: ViewModel202}\n"
+            + "  | {status: 404; body: ErrorResponse404}\n"
+            + "  | {status: number; body: ErrorResponse404}\n"
+        ),
+        generate_result_ts(post, &errors)
+      );
+    }
+
+    #[test]
+    fn it_from_yaml_recursive_refs() {
+      let yaml = "
+      paths:
+        /comments:
+          get:
+            summary: コメント一覧
+            operationId: get-comments
+            responses:
+              '200':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        replies:
                           type: array
                           items:
-                            type: string
-            operationId: get-users-userId
-            description: ユーザ詳細GET
+                            $ref: '#/components/schemas/Comment'
+      ";
+      let doc = &YamlLoader::load_from_str(yaml).unwrap()[0];
+
+      assert_eq!(
+        Some(Content::Object(vec![Property::new(
+          "replies",
+          Content::Array(Box::new(Content::Object(vec![])))
+        )])),
+        from_yaml(doc)[0].method_map["get"].response_opt
+      );
+    }
+
+    #[test]
+    fn it_from_yaml_media_types() {
+      let yaml = "
+      openapi: 3.0.0
+      paths:
+        /users/{userId}/avatar:
           put:
-            summary: ユーザ詳細PUT
-            operationId: put-users-userId
-            responses:
-              '200':
-                description: OK
+            summary: アバター更新
+            operationId: put-users-userId-avatar
             requestBody:
               content:
-                application/json:
+                multipart/form-data:
                   schema:
                     type: object
                     properties:
-                      hasDateAndPlace:
+                      file:
                         type: string
-                      location:
+                        format: binary
+                      caption:
+                        type: string
+                application/vnd.api+json:
+                  schema:
+                    type: object
+                    properties:
+                      url:
                         type: string
-                        enum:
-                          - S
-                          - A
-                          - B
-                          - NG
-                      timeMaybe:
-                        type: 
-                          - 'null'
-                          - object
-                        properties:
-                          hour:
-                            type: integer
-                          minute:
-                            type: integer
-            description: ユーザ詳細PUT
-        /users:
-          get:
-            summary: ユーザ取得
-            tags: []
             responses:
               '200':
-                description: OK
+                content:
+                  text/csv: {}
+              '400':
+                content:
+                  application/problem+json:
+                    schema:
+                      type: object
+                      properties:
+                        title:
+                          type: string
+      ";
+      let doc = &YamlLoader::load_from_str(yaml).unwrap()[0];
+      let method = from_yaml(doc)[0].method_map["put"].clone();
+
+      assert_eq!(
+        Some("application/vnd.api+json".to_string()),
+        method.request_media_type
+      );
+      assert_eq!(Some(Content::String), method.response_opt);
+      assert_eq!(
+        "text/csv",
+        method.response_map["200"].as_ref().unwrap().media_type
+      );
+      assert_eq!(
+        "application/problem+json",
+        method.response_map["400"].as_ref().unwrap().media_type
+      );
+
+      let form_doc = &YamlLoader::load_from_str(&yaml.replace(
+        "                application/vnd.api+json:\n                  schema:\n                    type: object\n                    properties:\n                      url:\n                        type: string\n",
+        "",
+      ))
+      .unwrap()[0];
+      let form_method = from_yaml(form_doc)[0].method_map["put"].clone();
+      assert_eq!(
+        Some("multipart/form-data".to_string()),
+        form_method.request_media_type
+      );
+      assert_eq!(
+        Some(Content::Object(vec![
+          Property::new("file", Content::Binary),
+          Property::new("caption", Content::String),
+        ])),
+        form_method.request_body_opt
+      );
+    }
+
+    #[test]
+    fn it_from_yaml_nullable_by_version() {
+      let schema = "
+        openapi: VERSION
+        paths:
+          /users:
+            post:
+              summary: ユーザ作成
+              operationId: post-users
+              requestBody:
                 content:
                   application/json:
                     schema:
-                      type: array
-                      items:
-                        type: object
-                        properties:
-                          userId:
-                            type: string
-                          age:
-                            type: integer
-                          family:
-                            type: object
-                            properties:
-                              name:
-                                type: string
-                              age:
-                                type: integer
-                          objList:
-                            type: array
-                            items:
-                              type: object
-                              properties:
-                                hoge:
-                                  type: string
-            operationId: get-users
-            description: ユーザ取得
-      operationId: get-users
-      description: ユーザ取得
-      components:
-        schemas: {}                 
-            ";
+                      type: object
+                      properties:
+                        name:
+                          type: string
+                          nullable: true
+                        kind:
+                          type: string
+                          const: member
+                        idOrName:
+                          type: [integer, string, 'null']
+                        point:
+                          type: array
+                          prefixItems:
+                            - type: number
+                            - type: number
+                          items:
+                            type: number
+      ";
+      let create_request_body = |openapi: &str| {
+        let doc = &YamlLoader::load_from_str(&schema.replace("VERSION", openapi)).unwrap()[0];
+        from_yaml(doc)[0].method_map["post"]
+          .request_body_opt
+          .clone()
+      };
 
-      let docs = YamlLoader::load_from_str(yaml).unwrap();
+      assert_eq!(
+        Some(Content::Object(vec![
+          Property {
+            or_null: true,
+            ..Property::new("name", Content::String)
+          },
+          Property::new("kind", Content::Enum(vec!["member".to_string()])),
+          Property {
+            or_null: true,
+            ..Property::new(
+              "idOrName",
+              Content::Union(vec![Content::Integer, Content::String])
+            )
+          },
+          Property::new("point", Content::Array(Box::new(Content::Number))),
+        ])),
+        create_request_body("3.0.3")
+      );
+      assert_eq!(
+        Some(Content::Object(vec![
+          Property::new("name", Content::String),
+          Property::new("kind", Content::Enum(vec!["member".to_string()])),
+          Property {
+            or_null: true,
+            ..Property::new(
+              "idOrName",
+              Content::Union(vec![Content::Integer, Content::String])
+            )
+          },
+          Property::new(
+            "point",
+            Content::Tuple(vec![Content::Number, Content::Number])
+          ),
+        ])),
+        create_request_body("3.1.0")
+      );
+    }
+
+    #[test]
+    fn it_from_yaml_const_and_examples() {
+      let yaml = "
+      openapi: 3.1.0
+      paths:
+        /users:
+          post:
+            summary: ユーザ作成
+            operationId: post-users
+            requestBody:
+              content:
+                application/json:
+                  schema:
+                    type: object
+                    properties:
+                      version:
+                        const: 2
+                      active:
+                        const: true
+                      name:
+                        type: string
+                        examples: [abab, cdcd]
+                      rank:
+                        enum: [1, 2, 2.5, null]
+                      updatedAt:
+                        type: string
+                        format: date-time
+                      id:
+                        type: string
+                        format: uuid
+      ";
+      let doc = &YamlLoader::load_from_str(yaml).unwrap()[0];
+
+      assert_eq!(
+        Some(Content::Object(vec![
+          Property::new("version", Content::Const(yaml_rust::Yaml::Integer(2))),
+          Property::new("active", Content::Const(yaml_rust::Yaml::Boolean(true))),
+          Property {
+            docs: Docs {
+              example: Some(yaml_rust::Yaml::String("abab".to_string())),
+              ..Docs::default()
+            },
+            ..Property::new("name", Content::String)
+          },
+          Property::new(
+            "rank",
+            Content::Union(vec![Content::Integer, Content::Number])
+          ),
+          Property::new("updatedAt", Content::String),
+          Property::new("id", Content::String),
+        ])),
+        from_yaml(doc)[0].method_map["post"].request_body_opt
+      );
+      let ts = generate_command_ts(
+        from_yaml(doc)[0].method_map["post"].clone(),
+        &TargetSettings::default(),
+      )
+      .unwrap();
+      assert!(ts.contains("version: 2;\nactive: true;"));
+      assert!(ts.contains("version: z.literal(2), active: z.literal(true)"));
+    }
+  }
+
+  #[test]
+  fn it_to_play_routings() {
+    let api = Api {
+      path: "/users/{userId}".to_string(),
+      param_map: linked_hash_map! {"userId".to_string() => ParamType::String},
+      method_map: linked_hash_map! {
+        "get".to_string() => Method{
+          operation_id: "get-users-userId".to_string(),
+          summary: "候補者詳細GET".to_string(),
+          response_opt: None,
+         request_body_opt: None,
+         request_media_type: None,
+         docs: Docs::default(),
+         response_map: LinkedHashMap::new(),
+         },
+        "put".to_string() => Method{
+          operation_id: "put-users-userId".to_string(),
+          summary: "候補者詳細PUT".to_string(),
+          response_opt:  None,
+          request_body_opt: None,
+          request_media_type: None,
+          docs: Docs::default(),
+          response_map: LinkedHashMap::new(),
+        },
+      },
+    };
+
+    let mut expected = vec![
+      "GET /users/:userId {Method Name}(userId: String)",
+      "PUT /users/:userId {Method Name}(userId: String)",
+    ];
+
+    let mut actual = to_play_routings(api, &[]);
+
+    expected.sort();
+    actual.sort();
+
+    assert_eq!(expected, actual);
+  }
+
+  #[test]
+  fn it_generate_command_scala() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![
+        Property::new("hasDateAndPlace", Content::String),
+        Property::new("location", Content::String),
+        Property::new("idList", Content::Array(Box::new(Content::String))),
+        Property::new(
+          "familyCommand",
+          Content::Object(vec![
+            Property::new("name", Content::String),
+            Property::new("age", Content::Integer),
+          ]),
+        ),
+      ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    assert_eq!(
+      Some(
+        "case class Command(hasDateAndPlace: String,\nlocation: String,\nidList: Seq[String],\nfamilyCommand: FamilyCommand)"
+          .to_string()
+          + "\nobject Command {\n"
+          + "  import play.api.libs.functional.syntax._\n  import play.api.libs.json._\n\n"
+          + "  implicit val reads: Reads[Command] = (\n"
+          + "    (__ \\ \"hasDateAndPlace\").read[String] and\n"
+          + "    (__ \\ \"location\").read[String] and\n"
+          + "    (__ \\ \"idList\").read[Seq[String]] and\n"
+          + "    (__ \\ \"familyCommand\").read[FamilyCommand]\n"
+          + "  )(Command.apply _)\n}\n"
+          + "case class FamilyCommand(name: String,\nage: Int or Long)\n"
+          + "object FamilyCommand {\n"
+          + "  import play.api.libs.functional.syntax._\n  import play.api.libs.json._\n\n"
+          + "  implicit val reads: Reads[FamilyCommand] = (\n"
+          + "    (__ \\ \"name\").read[String] and\n"
+          + "    (__ \\ \"age\").read[Int or Long]\n"
+          + "  )(FamilyCommand.apply _)\n}\n"
+      ),
+      generate_command_scala(method, &TargetSettings::default())
+    )
+  }
+  #[test]
+  fn it_generate_command_ts() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![
+        Property::new("hasDateAndPlace", Content::String),
+        Property::new("location", Content::String),
+        Property::new("idList", Content::Array(Box::new(Content::String))),
+        Property::new(
+          "familyCommand",
+          Content::Object(vec![
+            Property::new("name", Content::String),
+            Property::new("age", Content::Integer),
+          ]),
+        ),
+      ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    assert_eq!(
+      Some(
+        "export type Command={hasDateAndPlace: string;\nlocation: string;\nidList: string[];\nfamilyCommand: FamilyCommand}".to_string()
+        .to_string()
+          + "\nexport type FamilyCommand={name: string;\nage: number}\n"
+      ),
+      generate_command_ts(method, &TargetSettings::default())
+    )
+  }
+
+  #[test]
+  fn it_generate_command_rust() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![
+        Property {
+          or_null: true,
+          ..Property::new("hasDateAndPlace", Content::Date)
+        },
+        Property {
+          required: true,
+          ..Property::new(
+            "location",
+            Content::Enum(vec![
+              "S".to_string(),
+              "not-good".to_string(),
+              "not_good".to_string(),
+              "1st".to_string(),
+            ]),
+          )
+        },
+        Property {
+          required: true,
+          ..Property::new("idList", Content::Array(Box::new(Content::Integer)))
+        },
+        Property {
+          required: true,
+          ..Property::new(
+            "family",
+            Content::Object(vec![Property {
+              required: true,
+              ..Property::new("type", Content::String)
+            }]),
+          )
+        },
+        Property::new(
+          "code",
+          Content::Union(vec![Content::Integer, Content::String]),
+        ),
+      ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    assert_eq!(
+      Some(
+        "use serde::{Deserialize, Serialize};\nuse serde_json::Value;\n\n".to_string()
+          + "#[derive(Serialize, Deserialize)]\npub struct Command {\n"
+          + "  #[serde(rename = \"hasDateAndPlace\")]\n"
+          + "  #[serde(default, skip_serializing_if = \"Option::is_none\")]\n"
+          + "  pub has_date_and_place: Option<chrono::NaiveDate>,\n"
+          + "  pub location: CommandLocation,\n"
+          + "  #[serde(rename = \"idList\")]\n  pub id_list: Vec<i64>,\n"
+          + "  pub family: CommandFamily,\n"
+          + "  #[serde(default, skip_serializing_if = \"Option::is_none\")]\n"
+          + "  pub code: Option<Value>,\n}\n"
+          + "\n#[derive(Serialize, Deserialize)]\npub enum CommandLocation {\n  S,\n"
+          + "  #[serde(rename = \"not-good\")]\n  NotGood,\n"
+          + "  #[serde(rename = \"not_good\")]\n  NotGood2,\n"
+          + "  #[serde(rename = \"1st\")]\n  V1st,\n}\n"
+          + "\n#[derive(Serialize, Deserialize)]\npub struct CommandFamily {\n  pub r#type: String,\n}\n"
+      ),
+      generate_command_rust(method, &TargetSettings::default())
+    )
+  }
+
+  #[test]
+  fn it_generate_elm() {
+    let method = Method {
+      operation_id: "get-users-userId".to_string(),
+      summary: "候補者詳細GET".to_string(),
+      response_opt: Some(Content::Object(vec![
+        Property {
+          or_null: true,
+          ..Property::new("user_name", Content::String)
+        },
+        Property::new("nickname", Content::String),
+        Property {
+          required: true,
+          ..Property::new("tags", Content::Array(Box::new(Content::Integer)))
+        },
+      ])),
+      request_body_opt: None,
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    assert_eq!(
+      Some(
+        "module GetUsersUserId exposing (..)\n\n".to_string()
+          + "import Json.Decode as Decode exposing (Decoder)\n"
+          + "import Json.Decode.Pipeline exposing (optional, required)\n"
+          + "import Json.Encode as Encode\n\n\n"
+          + "type alias ViewModel =\n    { userName : Maybe String\n    , nickname : Maybe String\n    , tags : List Int\n    }\n\n\n"
+          + "viewModelDecoder : Decoder ViewModel\nviewModelDecoder =\n    Decode.succeed ViewModel\n"
+          + "        |> optional \"user_name\" (Decode.nullable Decode.string) Nothing\n"
+          + "        |> optional \"nickname\" (Decode.nullable Decode.string) Nothing\n"
+          + "        |> required \"tags\" (Decode.list Decode.int)\n\n\n"
+          + "encodeViewModel : ViewModel -> Encode.Value\nencodeViewModel value =\n    Encode.object\n"
+          + "        [ ( \"user_name\", (Maybe.map Encode.string >> Maybe.withDefault Encode.null) value.userName )\n"
+          + "        , ( \"nickname\", (Maybe.map Encode.string >> Maybe.withDefault Encode.null) value.nickname )\n"
+          + "        , ( \"tags\", (Encode.list Encode.int) value.tags )\n"
+          + "        ]\n"
+      ),
+      generate_elm(method, &TargetSettings::default())
+    )
+  }
+
+  #[test]
+  fn it_generate_view_model_python() {
+    let method = Method {
+      operation_id: "get-users".to_string(),
+      summary: "ユーザ取得".to_string(),
+      response_opt: Some(Content::Array(Box::new(Content::Object(vec![
+        Property::new("userId", Content::String),
+        Property {
+          or_null: true,
+          ..Property::new("birthday", Content::Date)
+        },
+      ])))),
+      request_body_opt: None,
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    assert_eq!(
+      Some(
+        "import datetime\nfrom typing import Optional\n\nfrom pydantic import BaseModel, Field\n\n\n"
+          .to_string()
+          + "class ViewModelItem(BaseModel):\n"
+          + "    user_id: str = Field(alias=\"userId\")\n"
+          + "    birthday: Optional[datetime.date]\n\n\n"
+          + "ViewModel = list[ViewModelItem]\n"
+      ),
+      generate_view_model_python(method.clone(), PythonStyle::Pydantic, PythonEnums::Literal, &TargetSettings::default())
+    );
+    assert_eq!(
+      Some(
+        "import datetime\nfrom dataclasses import dataclass, field\nfrom typing import Optional\n\n\n"
+          .to_string()
+          + "@dataclass\nclass ViewModelItem:\n"
+          + "    user_id: str = field(metadata={\"alias\": \"userId\"})\n"
+          + "    birthday: Optional[datetime.date]\n\n\n"
+          + "ViewModel = list[ViewModelItem]\n"
+      ),
+      generate_view_model_python(method, PythonStyle::Dataclass, PythonEnums::Literal, &TargetSettings::default())
+    );
+
+    // nested types are named after their parents, so equal keys do not collide
+    let property = |key: &str, value: Content| Property {
+      required: true,
+      ..Property::new(key, value)
+    };
+    let address = || {
+      property(
+        "address",
+        Content::Object(vec![property("city", Content::String)]),
+      )
+    };
+    let method = Method {
+      operation_id: "get-users".to_string(),
+      summary: "ユーザ取得".to_string(),
+      response_opt: Some(Content::Object(vec![
+        property("home", Content::Object(vec![address()])),
+        property("office", Content::Object(vec![address()])),
+      ])),
+      request_body_opt: None,
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    let python = generate_view_model_python(
+      method,
+      PythonStyle::Pydantic,
+      PythonEnums::Literal,
+      &TargetSettings::default(),
+    )
+    .unwrap();
+    assert!(python.contains("class ViewModelHomeAddress(BaseModel):"));
+    assert!(python.contains("class ViewModelOfficeAddress(BaseModel):"));
 
-      // Multi document support, doc is a yaml::Yaml
-      let doc = &docs[0];
+    let method = Method {
+      operation_id: "get-users".to_string(),
+      summary: "ユーザ取得".to_string(),
+      response_opt: Some(Content::Object(vec![property(
+        "status",
+        Content::Enum(vec![
+          "it's".to_string(),
+          "a\\b".to_string(),
+          "a-b".to_string(),
+          "a_b".to_string(),
+          "1st".to_string(),
+        ]),
+      )])),
+      request_body_opt: None,
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    let python = generate_view_model_python(
+      method.clone(),
+      PythonStyle::Pydantic,
+      PythonEnums::Literal,
+      &TargetSettings::default(),
+    )
+    .unwrap();
+    assert!(python.contains("    status: Literal[\"it's\", 'a\\\\b', 'a-b', 'a_b', '1st']\n"));
+    assert_eq!(
+      Some(
+        "from enum import Enum\n\nfrom pydantic import BaseModel\n\n\n".to_string()
+          + "class ViewModelStatus(str, Enum):\n"
+          + "    IT_S = \"it's\"\n"
+          + "    A_B = 'a\\\\b'\n"
+          + "    A_B2 = 'a-b'\n"
+          + "    A_B3 = 'a_b'\n"
+          + "    V_1ST = '1st'\n\n\n"
+          + "class ViewModel(BaseModel):\n"
+          + "    status: ViewModelStatus\n"
+      ),
+      generate_view_model_python(
+        method,
+        PythonStyle::Pydantic,
+        PythonEnums::Enum,
+        &TargetSettings::default()
+      )
+    );
+  }
 
-      let vec: Vec<Api> = vec![
-        Api {
-          path: "/users/{userId}".to_string(),
-          param_map: hashmap! {"userId".to_string() => ParamType::String},
-          method_map: hashmap! {
-            "get".to_string() => Method{
-              operation_id: "get-users-userId".to_string(),
-              summary: "ユーザ詳細GET".to_string(),
-              response_opt: Some(Content::Object(vec![
-                Property{key: "hogeId".to_string(), value: Content::Boolean, or_null: false},
-                Property{key: "foo".to_string(), value: Content::Integer, or_null: true},
-                Property{key: "bar_at".to_string(), value: Content::Date, or_null: false},
-                Property{key: "strList".to_string(), value: Content::Array(Box::new(Content::String)), or_null: false}
-              ])),
-             request_body_opt: None
-             },
-            "put".to_string() => Method{
-              operation_id: "put-users-userId".to_string(),
-              summary: "ユーザ詳細PUT".to_string(),
-              response_opt:  None,
-              request_body_opt:  Some(Content::Object(vec![
-                Property{key: "hasDateAndPlace".to_string(), value: Content::String, or_null: false},
-                Property{key: "location".to_string(), value: Content::String, or_null: false},
-                Property{key: "timeMaybe".to_string(), value: Content::Object(vec![
-                    Property{key: "hour".to_string(), value: Content::Integer, or_null: false},
-                    Property{key: "minute".to_string(), value: Content::Integer, or_null: false}
-                ]), or_null: true},
-              ]))
-            },
-          },
+  #[test]
+  fn it_generate_command_go() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![
+        Property {
+          or_null: true,
+          required: true,
+          ..Property::new("bar_at", Content::Date)
         },
-        Api {
-          path: "/users".to_string(),
-          param_map: HashMap::new(),
-          method_map: hashmap! {
-            "get".to_string() => Method{
-              operation_id: "get-users".to_string(),
-              summary: "ユーザ取得".to_string(),
-              response_opt: Some(Content::Array(Box::new(Content::Object(vec![
-                Property{key: "userId".to_string(), value: Content::String, or_null: false},
-                Property{key: "age".to_string(), value: Content::Integer, or_null: false},
-                Property{key: "family".to_string(), value: Content::Object(vec![
-                  Property{key: "name".to_string(), value: Content::String, or_null: false},
-                  Property{key: "age".to_string(), value: Content::Integer, or_null: false}
-                ]), or_null: false},
-                Property{key: "objList".to_string(), value:
-                 Content::Array(Box::new(Content::Object(vec![
-                  Property{key: "hoge".to_string(), value: Content::String, or_null: false},
-                 ]))), or_null: false}],
-              )))),
-             request_body_opt: None
-             },
-          },
+        Property {
+          required: true,
+          ..Property::new(
+            "location",
+            Content::Enum(vec!["S".to_string(), "NG".to_string()]),
+          )
         },
-      ];
-
-      assert_eq!(vec, from_yaml(&doc));
-    }
+        Property::new("idList", Content::Array(Box::new(Content::Integer))),
+        Property::new("age", Content::Integer),
+        Property {
+          required: true,
+          ..Property::new("écoleNom", Content::String)
+        },
+      ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    assert_eq!(
+      Some(
+        "package command\n\nimport \"time\"\n\n".to_string()
+          + "type Command struct {\n"
+          + "\tBarAt    *time.Time      `json:\"bar_at\"`\n"
+          + "\tLocation CommandLocation `json:\"location\"`\n"
+          + "\tIdList   []int64         `json:\"idList,omitempty\"`\n"
+          + "\tAge      *int64          `json:\"age,omitempty\"`\n"
+          + "\tÉcoleNom string          `json:\"écoleNom\"`\n"
+          + "}\n\n"
+          + "type CommandLocation string\n\nconst (\n"
+          + "\tCommandLocationS  CommandLocation = \"S\"\n"
+          + "\tCommandLocationNG CommandLocation = \"NG\"\n"
+          + ")\n"
+      ),
+      generate_command_go(method, &TargetSettings::default())
+    )
   }
 
   #[test]
-  fn it_to_play_routings() {
-    let api = Api {
-      path: "/users/{userId}".to_string(),
-      param_map: hashmap! {"userId".to_string() => ParamType::String},
-      method_map: hashmap! {
-        "get".to_string() => Method{
-          operation_id: "get-users-userId".to_string(),
-          summary: "候補者詳細GET".to_string(),
-          response_opt: None,
-         request_body_opt: None
-         },
-        "put".to_string() => Method{
-          operation_id: "put-users-userId".to_string(),
-          summary: "候補者詳細PUT".to_string(),
-          response_opt:  None,
-          request_body_opt: None
+  fn it_generate_command_java() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![
+        Property {
+          or_null: true,
+          ..Property::new("bar_at", Content::Date)
         },
-      },
+        Property::new(
+          "status",
+          Content::Enum(vec!["inReview".to_string(), "NG".to_string()]),
+        ),
+      ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
     };
+    assert_eq!(
+      Some(
+        "package put_users_user_id.command;\n\n".to_string()
+          + "import com.fasterxml.jackson.annotation.JsonProperty;\n"
+          + "import java.time.LocalDate;\n"
+          + "import java.util.Optional;\n\n"
+          + "public record Command(\n"
+          + "    @JsonProperty(\"bar_at\") Optional<LocalDate> barAt,\n"
+          + "    @JsonProperty(\"status\") CommandStatus status) {\n"
+          + "  public enum CommandStatus {\n"
+          + "    @JsonProperty(\"inReview\")\n    IN_REVIEW,\n"
+          + "    @JsonProperty(\"NG\")\n    NG\n"
+          + "  }\n"
+          + "}\n"
+      ),
+      generate_command_java(
+        method.clone(),
+        JavaStyle::Record,
+        &TargetSettings::default()
+      )
+    );
+    assert_eq!(
+      Some(
+        "package put_users_user_id.command;\n\n".to_string()
+          + "import com.fasterxml.jackson.annotation.JsonProperty;\n"
+          + "import java.time.LocalDate;\n"
+          + "import javax.annotation.Nullable;\n\n"
+          + "public class Command {\n"
+          + "  @Nullable\n  @JsonProperty(\"bar_at\")\n  private LocalDate barAt;\n"
+          + "  @JsonProperty(\"status\")\n  private CommandStatus status;\n\n"
+          + "  public LocalDate getBarAt() {\n    return barAt;\n  }\n\n"
+          + "  public void setBarAt(LocalDate barAt) {\n    this.barAt = barAt;\n  }\n\n"
+          + "  public CommandStatus getStatus() {\n    return status;\n  }\n\n"
+          + "  public void setStatus(CommandStatus status) {\n    this.status = status;\n  }\n\n"
+          + "  public enum CommandStatus {\n"
+          + "    @JsonProperty(\"inReview\")\n    IN_REVIEW,\n"
+          + "    @JsonProperty(\"NG\")\n    NG\n"
+          + "  }\n"
+          + "}\n"
+      ),
+      generate_command_java(method, JavaStyle::Pojo, &TargetSettings::default())
+    );
+  }
 
-    let mut expected = vec![
-      "GET /users/:userId {Method Name}(userId: String)",
-      "PUT /users/:userId {Method Name}(userId: String)",
-    ];
+  #[test]
+  fn it_generate_constraints() {
+    let yaml = "
+    paths:
+      /users:
+        post:
+          summary: ユーザ登録
+          operationId: post-users
+          requestBody:
+            content:
+              application/json:
+                schema:
+                  type: object
+                  required: [name, age]
+                  properties:
+                    name:
+                      type: string
+                      minLength: 1
+                      maxLength: 10
+                      pattern: '^[a-z]+$'
+                    age:
+                      type: integer
+                      minimum: 0
+                      maximum: 150.5
+          responses:
+            '201':
+              description: created
+    ";
+    let apis = from_yaml(&yaml_rust::YamlLoader::load_from_str(yaml).unwrap()[0]);
+    let method = apis[0].method_map["post"].clone();
+    let constraints = match &method.request_body_opt {
+      Some(Content::Object(properties)) => properties[1].constraints.clone(),
+      _ => panic!("expected object"),
+    };
+    assert_eq!(
+      Constraints {
+        minimum: Some(0.0),
+        maximum: Some(150.5),
+        ..Constraints::default()
+      },
+      constraints
+    );
 
-    let mut actual = to_play_routings(api);
+    assert_eq!(
+      Some(
+        "case class Command(name: String,
+age: Int or Long)
+object Command {
+  import play.api.libs.functional.syntax._
+  import play.api.libs.json._
 
-    expected.sort();
-    actual.sort();
+  implicit val reads: Reads[Command] = (
+    (__ \\ \"name\").read[String](Reads.minLength[String](1) keepAnd Reads.maxLength[String](10) keepAnd Reads.pattern(\"\"\"^[a-z]+$\"\"\".r)) and
+    (__ \\ \"age\").read[Int or Long](Reads.min[Long](0L) keepAnd Reads.max[Long](150L))
+  )(Command.apply _)
+}
+"
+        .to_string()
+      ),
+      generate_command_scala(method.clone(), &TargetSettings::default())
+    );
+    assert_eq!(
+      Some(
+        "import { z } from \"zod\";
 
-    assert_eq!(expected, actual);
+export type Command={name: string;
+age: number}
+
+export const CommandSchema = z.object({ name: z.string().min(1).max(10).regex(/^[a-z]+$/), age: z.number().int().gte(0).lte(150.5) });
+"
+        .to_string()
+      ),
+      generate_command_ts(method.clone(), &TargetSettings::default())
+    );
+    assert!(generate_command_rust(method, &TargetSettings::default())
+      .unwrap()
+      .contains("  /// minLength: 1, maxLength: 10, pattern: ^[a-z]+$\n  pub name: String,"));
   }
 
   #[test]
-  fn it_generate_command_scala() {
+  fn it_generate_docs() {
+    let yaml = "
+    paths:
+      /users:
+        post:
+          summary: ユーザ登録
+          description: 新しいユーザを登録する
+          deprecated: true
+          externalDocs:
+            url: https://example.com/users
+          operationId: post-users
+          requestBody:
+            content:
+              application/json:
+                schema:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                      title: 名前
+                      example: abab
+                    age:
+                      type: integer
+          responses:
+            '201':
+              description: created
+    ";
+    let apis = from_yaml(&yaml_rust::YamlLoader::load_from_str(yaml).unwrap()[0]);
+    let method = apis[0].method_map["post"].clone();
+
+    assert_eq!(
+      Some(
+        "/**
+ * 新しいユーザを登録する
+ * @deprecated
+ * @see https://example.com/users
+ *
+ * @param name 名前 @example \"abab\"
+ */
+case class Command(name: String,
+age: Int or Long)
+object Command {
+  import play.api.libs.functional.syntax._
+  import play.api.libs.json._
+
+  implicit val reads: Reads[Command] = (
+    (__ \\ \"name\").read[String] and
+    (__ \\ \"age\").read[Int or Long]
+  )(Command.apply _)
+}
+"
+        .to_string()
+      ),
+      generate_command_scala(method.clone(), &TargetSettings::default())
+    );
+    assert_eq!(
+      Some(
+        "/**
+ * 新しいユーザを登録する
+ * @deprecated
+ * @see https://example.com/users
+ */
+export type Command={/**
+ * 名前
+ * @example
+ * \"abab\"
+ */
+name: string;
+age: number}
+"
+        .to_string()
+      ),
+      generate_command_ts(method, &TargetSettings::default())
+    );
+    assert_eq!(
+      vec!["# 新しいユーザを登録する\n# @deprecated\n# @see https://example.com/users\nPOST /users {Method Name}()"],
+      to_play_routings(apis[0].clone(), &[])
+    );
+  }
+
+  #[test]
+  fn it_generate_escaped_names() {
     let method = Method {
       operation_id: "put-users-userId".to_string(),
       summary: "候補者詳細PUT".to_string(),
       response_opt: None,
       request_body_opt: Some(Content::Object(vec![
         Property {
-          key: "hasDateAndPlace".to_string(),
-          value: Content::String,
-          or_null: false,
+          required: true,
+          ..Property::new("type", Content::String)
         },
         Property {
-          key: "location".to_string(),
-          value: Content::String,
-          or_null: false,
+          required: true,
+          ..Property::new("bar-at", Content::Object(vec![]))
+        },
+      ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    assert_eq!(
+      Some(
+        "case class Command(`type`: String,\nbarAt: BarAt)\nobject Command {\n".to_string()
+          + "  import play.api.libs.functional.syntax._\n  import play.api.libs.json._\n\n"
+          + "  implicit val reads: Reads[Command] = (\n"
+          + "    (__ \\ \"type\").read[String] and\n"
+          + "    (__ \\ \"bar-at\").read[BarAt]\n"
+          + "  )(Command.apply _)\n"
+          + "  implicit val writes: OWrites[Command] = (\n"
+          + "    (__ \\ \"type\").write[String] and\n"
+          + "    (__ \\ \"bar-at\").write[BarAt]\n"
+          + "  )(unlift(Command.unapply))\n}\n"
+          + "case class BarAt()\nobject BarAt {\n"
+          + "  import play.api.libs.functional.syntax._\n  import play.api.libs.json._\n\n"
+          + "  implicit val reads: Reads[BarAt] = Reads.pure(BarAt())\n"
+          + "  implicit val writes: OWrites[BarAt] = OWrites(_ => Json.obj())\n}\n"
+      ),
+      generate_command_scala(method.clone(), &TargetSettings::default())
+    );
+    assert_eq!(
+      Some(
+        "export type Command={type: string;\n\"bar-at\": BarAt}\nexport type BarAt={}\n"
+          .to_string()
+      ),
+      generate_command_ts(method.clone(), &TargetSettings::default())
+    );
+    assert!(generate_command_rust(method, &TargetSettings::default())
+      .unwrap()
+      .contains(
+        "  pub r#type: String,\n  #[serde(rename = \"bar-at\")]\n  pub bar_at: CommandBarAt,"
+      ));
+  }
+
+  #[test]
+  fn it_generate_with_naming() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![Property {
+        constraints: Constraints {
+          min_length: Some(1),
+          ..Constraints::default()
         },
+        ..Property::new("user_name", Content::String)
+      }])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    let scala = generate_command_scala(method.clone(), &TargetSettings::default()).unwrap();
+    assert!(scala.starts_with("case class Command(userName: String)\n"));
+    assert!(scala.contains(
+      "  implicit val writes: OWrites[Command] = (__ \\ \"user_name\").write[String].contramap[Command](_.userName)"
+    ));
+    let key_naming = TargetSettings {
+      naming: Some(Naming::Key),
+      ..TargetSettings::default()
+    };
+    let scala = generate_command_scala(method.clone(), &key_naming).unwrap();
+    assert!(scala.starts_with("case class Command(user_name: String)\n"));
+    assert!(!scala.contains("writes"));
+
+    let camel_case = TargetSettings {
+      naming: Some(Naming::CamelCase),
+      ..TargetSettings::default()
+    };
+    assert_eq!(
+      Some(
+        "import { z } from \"zod\";\n\nexport type Command={userName: string}\n".to_string()
+          + "export const CommandKeys={userName: \"user_name\"} as const;\n"
+          + "\nexport const CommandSchema = z.object({ user_name: z.string().min(1) })"
+          + ".transform((value) => ({ userName: value[\"user_name\"] }));\n"
+      ),
+      generate_command_ts(method, &camel_case)
+    );
+  }
+
+  #[test]
+  fn it_generate_arbitrary_scala() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![
+        Property::new(
+          "role",
+          Content::Enum(vec!["admin".to_string(), "member".to_string()]),
+        ),
         Property {
-          key: "idList".to_string(),
-          value: Content::Array(Box::new(Content::String)),
-          or_null: false,
+          or_null: true,
+          ..Property::new("nickname", Content::String)
         },
         Property {
-          key: "familyCommand".to_string(),
-          value: Content::Object(vec![
-            Property {
-              key: "name".to_string(),
-              value: Content::String,
-              or_null: false,
-            },
-            Property {
-              key: "age".to_string(),
-              value: Content::Integer,
-              or_null: false,
-            },
-          ]),
-          or_null: false,
+          constraints: Constraints {
+            minimum: Some(1.0),
+            ..Constraints::default()
+          },
+          ..Property::new("age", Content::Integer)
         },
+        Property::new(
+          "familyCommand",
+          Content::Object(vec![Property::new("birthday", Content::Date)]),
+        ),
       ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
     };
     assert_eq!(
       Some(
-        "case class Command(hasDateAndPlace: String,\nlocation: String,\nidList: Seq[String],\nfamilyCommand: FamilyCommand)"
-          .to_string()
-          + "\ncase class FamilyCommand(name: String,\nage: Int or Long)\n"
+        "import java.time._
+import org.scalacheck.{Arbitrary, Gen}
+import play.api.libs.Files.SingletonTemporaryFileCreator
+
+object CommandArbitraries {
+  implicit lazy val arbFamilyCommand: Arbitrary[FamilyCommand] = Arbitrary(
+    for {
+      birthday <- Gen.chooseNum(0L, 4102444800L).map(s => ZonedDateTime.ofInstant(Instant.ofEpochSecond(s), ZoneOffset.UTC))
+    } yield FamilyCommand(birthday)
+  )
+
+  implicit lazy val arbCommand: Arbitrary[Command] = Arbitrary(
+    for {
+      role <- Gen.oneOf(\"admin\", \"member\")
+      nickname <- Gen.option(Gen.alphaNumStr)
+      age <- Gen.chooseNum[Long](1L, Long.MaxValue)
+      familyCommand <- Arbitrary.arbitrary[FamilyCommand]
+    } yield Command(role, nickname, age, familyCommand)
+  )
+}
+"
+        .to_string()
       ),
-      generate_command_scala(method)
-    )
+      generate_arbitrary_scala(method, &TargetSettings::default())
+    );
   }
+
   #[test]
-  fn it_generate_command_ts() {
+  fn it_generate_nullable_view_model() {
+    let method = Method {
+      operation_id: "get-users-userId".to_string(),
+      summary: "候補者詳細GET".to_string(),
+      response_opt: Some(Content::Object(vec![Property {
+        or_null: true,
+        constraints: Constraints {
+          max_length: Some(20),
+          ..Constraints::default()
+        },
+        ..Property::new("nickname", Content::String)
+      }])),
+      request_body_opt: None,
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    assert_eq!(
+      Some(
+        "case class ViewModel(nickname: Option[String])\nobject ViewModel {\n".to_string()
+          + "  import play.api.libs.functional.syntax._\n  import play.api.libs.json._\n\n"
+          + "  implicit val reads: Reads[ViewModel] = (__ \\ \"nickname\").readNullable[String](Reads.maxLength[String](20)).map(ViewModel.apply)\n}\n"
+      ),
+      generate_view_model_scala(method.clone(), &TargetSettings::default())
+    );
+    assert_eq!(
+      Some(
+        "import { z } from \"zod\";\n\nexport type ViewModel={nickname: string | null}\n".to_string()
+          + "\nexport const ViewModelSchema = z.object({ nickname: z.string().max(20).nullable() });\n"
+      ),
+      generate_view_model_ts(method, &TargetSettings::default())
+    );
+  }
+
+  #[test]
+  fn it_generate_arbitrary_scala_nested_arrays() {
     let method = Method {
       operation_id: "put-users-userId".to_string(),
       summary: "候補者詳細PUT".to_string(),
       response_opt: None,
       request_body_opt: Some(Content::Object(vec![
         Property {
-          key: "hasDateAndPlace".to_string(),
-          value: Content::String,
-          or_null: false,
+          constraints: Constraints {
+            minimum: Some(0.0),
+            maximum: Some(150.0),
+            ..Constraints::default()
+          },
+          ..Property::new("age", Content::Integer)
         },
+        Property::new(
+          "label",
+          Content::Enum(vec!["café".to_string(), "a\"b\n".to_string()]),
+        ),
         Property {
-          key: "location".to_string(),
-          value: Content::String,
-          or_null: false,
+          constraints: Constraints {
+            pattern: Some("^\"\"\"\\w+$".to_string()),
+            ..Constraints::default()
+          },
+          ..Property::new("quote", Content::String)
         },
+        Property::new(
+          "pets",
+          Content::Array(Box::new(Content::Object(vec![Property::new(
+            "name",
+            Content::String,
+          )]))),
+        ),
+      ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    let settings = TargetSettings {
+      types: hashmap! {"integer".to_string() => "Int".to_string()},
+      ..TargetSettings::default()
+    };
+    let scala = generate_command_scala(method.clone(), &settings).unwrap();
+    assert!(scala.contains("age: Int,"));
+    assert!(scala.contains("pets: Seq[Pets]"));
+    assert!(scala.contains("case class Pets(name: String)"));
+    assert!(scala.contains("read[Int](Reads.min[Int](0) keepAnd Reads.max[Int](150))"));
+    assert!(scala.contains("Reads.pattern(\"^\\\"\\\"\\\"\\\\w+$\".r)"));
+    let arbitrary = generate_arbitrary_scala(method.clone(), &settings).unwrap();
+    assert!(arbitrary.contains("age <- Gen.chooseNum[Int](0, 150)"));
+    assert!(arbitrary.contains("label <- Gen.oneOf(\"café\", \"a\\\"b\\n\")"));
+    assert!(arbitrary.contains("implicit lazy val arbPets: Arbitrary[Pets]"));
+    assert!(arbitrary.contains("pets <- Gen.listOf(Arbitrary.arbitrary[Pets])"));
+
+    let list = Method {
+      response_opt: Some(Content::Array(Box::new(Content::Object(vec![
+        Property::new("name", Content::String),
+      ])))),
+      request_body_opt: None,
+      ..method
+    };
+    assert!(generate_view_model_scala(list.clone(), &settings)
+      .unwrap()
+      .starts_with("case class ViewModelItem(name: String)\n"));
+    assert!(generate_arbitrary_scala(list, &settings).unwrap().contains(
+      "  lazy val genViewModel: Gen[Seq[ViewModelItem]] = Gen.listOf(Arbitrary.arbitrary[ViewModelItem])"
+    ));
+  }
+
+  #[test]
+  fn it_generate_ts_arrays_of_objects() {
+    let label = Property {
+      required: true,
+      ..Property::new("label", Content::String)
+    };
+    let method = Method {
+      operation_id: "get-users".to_string(),
+      summary: "ユーザー一覧".to_string(),
+      response_opt: Some(Content::Object(vec![
         Property {
-          key: "idList".to_string(),
-          value: Content::Array(Box::new(Content::String)),
-          or_null: false,
+          required: true,
+          ..Property::new(
+            "tags",
+            Content::Array(Box::new(Content::Object(vec![label.clone()]))),
+          )
         },
         Property {
-          key: "familyCommand".to_string(),
-          value: Content::Object(vec![
-            Property {
-              key: "name".to_string(),
-              value: Content::String,
-              or_null: false,
-            },
-            Property {
-              key: "age".to_string(),
-              value: Content::Integer,
-              or_null: false,
-            },
-          ]),
-          or_null: false,
+          required: true,
+          ..Property::new(
+            "owner",
+            Content::Union(vec![Content::String, Content::Object(vec![label])]),
+          )
+        },
+      ])),
+      request_body_opt: None,
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    let ts = generate_view_model_ts(method.clone(), &TargetSettings::default()).unwrap();
+    assert!(ts.contains("tags: Tags[];"));
+    assert!(ts.contains("export type Tags={label: string}"));
+    assert!(ts.contains("owner: string | {label: string}"));
+
+    let list = Method {
+      response_opt: Some(Content::Array(Box::new(Content::String))),
+      ..method
+    };
+    assert_eq!(
+      Some("export type ViewModel=string[]\n".to_string()),
+      generate_view_model_ts(list, &TargetSettings::default())
+    );
+  }
+
+  #[test]
+  fn it_generate_with_target_settings() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![
+        Property::new("age", Content::Integer),
+        Property::new("birthday", Content::Date),
+      ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    let settings = TargetSettings {
+      package: Some("com.example".to_string()),
+      types: hashmap! {
+        "integer".to_string() => "Long".to_string(),
+        "command.date".to_string() => "LocalDate".to_string(),
+      },
+      ..TargetSettings::default()
+    };
+    let scala = generate_command_scala(method.clone(), &settings).unwrap();
+    assert!(scala.starts_with("package com.example.put_users_user_id.command\n\n"));
+    assert!(scala.contains("case class Command(age: Long,\nbirthday: LocalDate)"));
+    assert_eq!(
+      Some("export type Command={age: number;\nbirthday: Date}\n".to_string()),
+      generate_command_ts(method, &TargetSettings::default())
+    );
+  }
+  #[test]
+  fn it_generate_with_other_target_settings() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: None,
+      request_body_opt: Some(Content::Object(vec![
+        Property {
+          required: true,
+          ..Property::new("userAge", Content::Integer)
+        },
+        Property {
+          required: true,
+          ..Property::new("birthday", Content::Date)
         },
       ])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    let settings = TargetSettings {
+      types: hashmap! {
+        "integer".to_string() => "i32".to_string(),
+        "command.date".to_string() => "String".to_string(),
+      },
+      naming: Some(Naming::CamelCase),
+      ..TargetSettings::default()
+    };
+    let rust = generate_command_rust(method.clone(), &settings).unwrap();
+    assert!(rust.contains("  pub userAge: i32,\n  pub birthday: String,"));
+    let go = generate_command_go(
+      method.clone(),
+      &TargetSettings {
+        types: hashmap! {"integer".to_string() => "int32".to_string()},
+        ..TargetSettings::default()
+      },
+    )
+    .unwrap();
+    assert!(go.contains("\tUserAge  int32     `json:\"userAge\"`\n"));
+    let python = generate_command_python(
+      method.clone(),
+      PythonStyle::Pydantic,
+      PythonEnums::Literal,
+      &TargetSettings {
+        naming: Some(Naming::Key),
+        ..TargetSettings::default()
+      },
+    )
+    .unwrap();
+    assert!(python.contains("    userAge: int\n"));
+    let java = generate_command_java(
+      method.clone(),
+      JavaStyle::Record,
+      &TargetSettings {
+        package: Some("com.example.api".to_string()),
+        types: hashmap! {"integer".to_string() => "Integer".to_string()},
+        ..TargetSettings::default()
+      },
+    )
+    .unwrap();
+    assert!(java.starts_with("package com.example.api.put_users_user_id.command;\n"));
+    assert!(java.contains("@JsonProperty(\"userAge\") Integer userAge"));
+    let elm = generate_elm(
+      method,
+      &TargetSettings {
+        types: hashmap! {"date".to_string() => "Posix".to_string()},
+        naming: Some(Naming::SnakeCase),
+        ..TargetSettings::default()
+      },
+    )
+    .unwrap();
+    assert!(elm.contains("{ user_age : Int\n    , birthday : Posix\n"));
+    assert!(elm.contains("|> required \"birthday\" posixDecoder"));
+    assert!(elm.contains("( \"birthday\", encodePosix value.birthday )"));
+  }
+
+  #[test]
+  fn it_generate_with_user_template() {
+    let method = Method {
+      operation_id: "put-users-userId".to_string(),
+      summary: "候補者詳細PUT".to_string(),
+      response_opt: Some(Content::String),
+      request_body_opt: Some(Content::Object(vec![Property {
+        or_null: true,
+        ..Property::new("tags", Content::Array(Box::new(Content::String)))
+      }])),
+      request_media_type: None,
+      docs: Docs::default(),
+      response_map: LinkedHashMap::new(),
+    };
+    let settings = TargetSettings {
+      templates: hashmap! {
+        "command".to_string() => Template::parse(
+          "// {{method.operation_id}}
+{{#each classes}}final case class {{name}}({{#each properties}}{{name}}: {{type}} /* {{content.kind}} of {{content.items.kind}} */{{/each}})
+{{/each}}",
+        )
+        .unwrap(),
+      },
+      ..TargetSettings::default()
     };
     assert_eq!(
       Some(
-        "type Command={hasDateAndPlace: string;\nlocation: string;\nidList: string[];\nfamilyCommand: FamilyCommand}".to_string()
-        .to_string()
-          + "\ntype FamilyCommand={name: string;\nage: number}\n"
+        "// put-users-userId\nfinal case class Command(tags: Option[Seq[String]] /* array of string */)\n"
+          .to_string()
       ),
-      generate_command_ts(method)
-    )
+      generate_command_scala(method.clone(), &settings)
+    );
+    assert_eq!(
+      Some("String".to_string()),
+      generate_view_model_scala(method, &settings)
+    );
   }
 }
+
+pub mod loader;
+
+pub mod swagger2;
+
+pub mod lint;
+
+pub mod diff;
+
+pub mod validate;
+
+pub mod mock;
+
+pub mod sample;
+
+pub mod naming;
+
+pub mod config;
+
+pub mod template;
+
+pub mod watch;
+
+pub mod emitter;
@@ -0,0 +1,439 @@
+use crate::apis::{self, Api, Method};
+use crate::{loader, sample};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+use yaml_rust::Yaml;
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Route {
+  pub path: String,
+  pub method_type: String,
+  pub segments: Vec<String>,
+  pub method: Method,
+}
+
+pub fn routes(apis: &[Api]) -> Vec<Route> {
+  let mut routes = apis
+    .iter()
+    .flat_map(|api| {
+      api
+        .method_map
+        .iter()
+        .map(move |(method_type, method)| Route {
+          path: api.path.clone(),
+          method_type: method_type.clone(),
+          segments: apis::nomalize_play_variable_path(api.path.clone())
+            .split('/')
+            .map(|segment| segment.to_string())
+            .collect(),
+          method: method.clone(),
+        })
+    })
+    .collect::<Vec<_>>();
+  // static segments win over variables, e.g. /users/me before /users/:userId
+  routes.sort_by_key(|route| {
+    (
+      route
+        .segments
+        .iter()
+        .filter(|segment| segment.starts_with(':'))
+        .count(),
+      route.path.clone(),
+    )
+  });
+  routes
+}
+
+/// Finds the route for a request path and returns it with the captured path variables.
+pub fn find_route<'a>(
+  routes: &'a [Route],
+  method_type: &str,
+  path: &str,
+) -> Option<(&'a Route, HashMap<String, String>)> {
+  let path = path.split('?').next().unwrap_or("");
+  let request_segments = path.split('/').collect::<Vec<_>>();
+  routes
+    .iter()
+    .filter(|route| route.method_type == method_type.to_lowercase())
+    .filter(|route| route.segments.len() == request_segments.len())
+    .find_map(|route| {
+      let mut variables = HashMap::new();
+      for (segment, request_segment) in route.segments.iter().zip(request_segments.iter()) {
+        if segment.starts_with(':') && !request_segment.is_empty() {
+          variables.insert(segment[1..].to_string(), request_segment.to_string());
+        } else if segment != request_segment {
+          return None;
+        }
+      }
+      Some((route, variables))
+    })
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Response {
+  pub status: u16,
+  pub media_type: String,
+  pub body: String,
+}
+
+fn status_code(status: &str) -> u16 {
+  status.replace("XX", "00").parse().unwrap_or(200)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+  match status {
+    100 => "Continue",
+    101 => "Switching Protocols",
+    200 => "OK",
+    201 => "Created",
+    202 => "Accepted",
+    203 => "Non-Authoritative Information",
+    204 => "No Content",
+    205 => "Reset Content",
+    206 => "Partial Content",
+    300 => "Multiple Choices",
+    301 => "Moved Permanently",
+    302 => "Found",
+    303 => "See Other",
+    304 => "Not Modified",
+    307 => "Temporary Redirect",
+    308 => "Permanent Redirect",
+    400 => "Bad Request",
+    401 => "Unauthorized",
+    402 => "Payment Required",
+    403 => "Forbidden",
+    404 => "Not Found",
+    405 => "Method Not Allowed",
+    406 => "Not Acceptable",
+    407 => "Proxy Authentication Required",
+    408 => "Request Timeout",
+    409 => "Conflict",
+    410 => "Gone",
+    411 => "Length Required",
+    412 => "Precondition Failed",
+    413 => "Payload Too Large",
+    414 => "URI Too Long",
+    415 => "Unsupported Media Type",
+    416 => "Range Not Satisfiable",
+    417 => "Expectation Failed",
+    418 => "I'm a teapot",
+    421 => "Misdirected Request",
+    422 => "Unprocessable Entity",
+    423 => "Locked",
+    424 => "Failed Dependency",
+    425 => "Too Early",
+    426 => "Upgrade Required",
+    428 => "Precondition Required",
+    429 => "Too Many Requests",
+    431 => "Request Header Fields Too Large",
+    451 => "Unavailable For Legal Reasons",
+    500 => "Internal Server Error",
+    501 => "Not Implemented",
+    502 => "Bad Gateway",
+    503 => "Service Unavailable",
+    504 => "Gateway Timeout",
+    505 => "HTTP Version Not Supported",
+    506 => "Variant Also Negotiates",
+    507 => "Insufficient Storage",
+    508 => "Loop Detected",
+    510 => "Not Extended",
+    511 => "Network Authentication Required",
+    _ => "Unknown",
+  }
+}
+
+fn error_response(status: u16, messages: Vec<String>) -> Response {
+  Response {
+    status,
+    media_type: "application/json".to_string(),
+    body: loader::to_json(&Yaml::Hash(
+      vec![(
+        Yaml::String("errors".to_string()),
+        Yaml::Array(messages.into_iter().map(Yaml::String).collect()),
+      )]
+      .into_iter()
+      .collect(),
+    )),
+  }
+}
+
+/// Answers a request from the spec: 204 for CORS preflights, 404 for unknown routes, 400 with
+/// every mismatch when the body does not match the Command schema, otherwise the example or
+/// synthesized ViewModel.
+pub fn handle(
+  document: &Yaml,
+  routes: &[Route],
+  method_type: &str,
+  path: &str,
+  body: &str,
+) -> Response {
+  if method_type.eq_ignore_ascii_case("OPTIONS") {
+    return Response {
+      status: 204,
+      media_type: "text/plain".to_string(),
+      body: "".to_string(),
+    };
+  }
+
+  let route = match find_route(routes, method_type, path) {
+    Some((route, _)) => route,
+    None => {
+      return error_response(
+        404,
+        vec![format!("no operation for {} {}", method_type, path)],
+      )
+    }
+  };
+
+  if let Some(command) = &route.method.request_body_opt {
+    let mismatches = if apis::is_json_media_type(
+      route
+        .method
+        .request_media_type
+        .as_deref()
+        .unwrap_or("application/json"),
+    ) {
+      match loader::load_from_str(body, loader::Format::Json) {
+        Ok(value) => crate::validate::validate(&value, command)
+          .into_iter()
+          .map(|mismatch| mismatch.to_string())
+          .collect(),
+        Err(err) => vec![format!("invalid JSON body: {}", err)],
+      }
+    } else {
+      vec![]
+    };
+    if !mismatches.is_empty() {
+      return error_response(400, mismatches);
+    }
+  }
+
+  let (status, value) = match sample::view_model_example(
+    document,
+    &route.path,
+    &route.method_type,
+    &route.method,
+    sample::Options::default(),
+  ) {
+    Some(example) => example,
+    None => {
+      return Response {
+        status: apis::primary_success_status(&route.method.response_map)
+          .map(|status| status_code(&status))
+          .unwrap_or(204),
+        media_type: "application/json".to_string(),
+        body: "".to_string(),
+      }
+    }
+  };
+  let response_body = route.method.response_map[&status]
+    .as_ref()
+    .expect("examples come from a response with content");
+
+  Response {
+    status: status_code(&status),
+    media_type: response_body.media_type.clone(),
+    body: match value {
+      Yaml::String(text) if !apis::is_json_media_type(&response_body.media_type) => text,
+      value => loader::to_json(&value),
+    },
+  }
+}
+
+/// Serves a loaded document; Swagger 2.0 is converted first, as the examples are looked up in
+/// the OpenAPI 3 layout.
+pub fn serve(document: &Yaml, port: u16) -> io::Result<()> {
+  let document = crate::swagger2::as_openapi3(document);
+  let routes = routes(&apis::from_yaml(&document));
+  let listener = TcpListener::bind(("127.0.0.1", port))?;
+  println!("mock server listening on http://localhost:{}", port);
+  for stream in listener.incoming() {
+    // a broken client must not take the whole server down
+    if let Err(err) = stream.and_then(|stream| respond(&document, &routes, stream)) {
+      eprintln!("connection failed: {}", err);
+    }
+  }
+  Ok(())
+}
+
+/// Request bodies above this are answered with 413 instead of being read.
+const MAX_BODY_LENGTH: usize = 10 * 1024 * 1024;
+
+fn respond(document: &Yaml, routes: &[Route], stream: TcpStream) -> io::Result<()> {
+  stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+  let mut reader = BufReader::new(stream.try_clone()?);
+  exchange(document, routes, &mut reader, stream)
+}
+
+fn exchange(
+  document: &Yaml,
+  routes: &[Route],
+  reader: &mut impl BufRead,
+  mut writer: impl Write,
+) -> io::Result<()> {
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line)?;
+  let mut content_length = 0;
+  loop {
+    let mut header = String::new();
+    if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+      break;
+    }
+    let mut parts = header.splitn(2, ':');
+    if parts.next().map(|name| name.trim().to_lowercase()) == Some("content-length".to_string()) {
+      content_length = parts
+        .next()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(0);
+    }
+  }
+
+  let mut request = request_line.split_whitespace();
+  let method_type = request.next().unwrap_or("GET");
+  let path = request.next().unwrap_or("/");
+  // the length comes from the client, it is checked before allocating the body
+  let response = if content_length > MAX_BODY_LENGTH {
+    error_response(
+      413,
+      vec![format!(
+        "request body of {} bytes exceeds the limit of {} bytes",
+        content_length, MAX_BODY_LENGTH
+      )],
+    )
+  } else {
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    handle(
+      document,
+      routes,
+      method_type,
+      path,
+      &String::from_utf8_lossy(&body),
+    )
+  };
+  println!("{} {} -> {}", method_type, path, response.status);
+  write!(
+    writer,
+    "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST, PUT, PATCH, DELETE, OPTIONS\r\nAccess-Control-Allow-Headers: *\r\nConnection: close\r\n\r\n{}",
+    response.status,
+    reason_phrase(response.status),
+    response.media_type,
+    response.body.len(),
+    response.body
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::apis::from_yaml;
+  use yaml_rust::YamlLoader;
+
+  #[test]
+  fn it_handle() {
+    let yaml = "
+      paths:
+        '/users/{userId}':
+          parameters:
+            - name: userId
+              in: path
+              schema:
+                type: string
+          get:
+            summary: ユーザ詳細GET
+            operationId: get-users-userId
+            responses:
+              '200':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        name:
+                          type: string
+                          example: abab
+                        age:
+                          type: integer
+                          example: 20
+          put:
+            summary: ユーザ詳細PUT
+            operationId: put-users-userId
+            requestBody:
+              content:
+                application/json:
+                  schema:
+                    type: object
+                    properties:
+                      name:
+                        type: string
+            responses:
+              '201':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                    example:
+                      ok: true
+        /users/me:
+          get:
+            summary: 自分
+            operationId: get-users-me
+            responses:
+              '200':
+                content:
+                  text/plain:
+                    example: me
+      ";
+    let document = &YamlLoader::load_from_str(yaml).unwrap()[0];
+    let routes = routes(&from_yaml(document));
+    let handle = |method: &str, path: &str, body: &str| {
+      let response = handle(document, &routes, method, path, body);
+      (response.status, response.body)
+    };
+
+    assert_eq!(
+      (
+        200,
+        "{\n  \"name\": \"abab\",\n  \"age\": 20\n}".to_string()
+      ),
+      handle("GET", "/users/42?verbose=true", "")
+    );
+    assert_eq!((200, "me".to_string()), handle("GET", "/users/me", ""));
+    assert_eq!(
+      (201, "{\n  \"ok\": true\n}".to_string()),
+      handle("PUT", "/users/42", "{\"name\": \"abab\"}")
+    );
+    assert_eq!(
+      (
+        400,
+        "{\n  \"errors\": [\n    \"$.name: expected string, found integer\"\n  ]\n}".to_string()
+      ),
+      handle("PUT", "/users/42", "{\"name\": 1}")
+    );
+    assert_eq!(404, handle("DELETE", "/users/42", "").0);
+    assert_eq!((204, "".to_string()), handle("OPTIONS", "/users/42", ""));
+  }
+
+  #[test]
+  fn it_exchange() {
+    let document = &YamlLoader::load_from_str("paths: {}").unwrap()[0];
+    let exchange = |request: String| {
+      let mut response = vec![];
+      exchange(document, &[], &mut request.as_bytes(), &mut response).unwrap();
+      String::from_utf8(response).unwrap()
+    };
+
+    assert!(
+      exchange("GET /users HTTP/1.1\r\n\r\n".to_string()).starts_with("HTTP/1.1 404 Not Found\r\n")
+    );
+    let too_large = exchange(format!(
+      "POST /users HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+      MAX_BODY_LENGTH + 1
+    ));
+    assert!(too_large.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+    assert!(too_large.contains("exceeds the limit"));
+    assert_eq!("Unknown", reason_phrase(599));
+  }
+}
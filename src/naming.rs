@@ -0,0 +1,383 @@
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Target {
+  Scala,
+  TypeScript,
+  Rust,
+  Python,
+  Go,
+  Java,
+  Elm,
+}
+
+/// Style of generated field names, the `naming` setting of a target.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Naming {
+  /// the JSON key itself
+  Key,
+  CamelCase,
+  SnakeCase,
+  PascalCase,
+}
+
+impl Naming {
+  pub const NAMES: [&'static str; 4] = ["key", "camelCase", "snake_case", "PascalCase"];
+
+  pub fn parse(name: &str) -> Option<Naming> {
+    match name {
+      "key" => Some(Naming::Key),
+      "camelCase" => Some(Naming::CamelCase),
+      "snake_case" => Some(Naming::SnakeCase),
+      "PascalCase" => Some(Naming::PascalCase),
+      _ => None,
+    }
+  }
+
+  /// Convention of the target language; TypeScript keeps the key so the types match the wire
+  /// format unless configured otherwise.
+  pub fn default_for(target: Target) -> Naming {
+    match target {
+      Target::TypeScript => Naming::Key,
+      Target::Scala | Target::Java | Target::Elm => Naming::CamelCase,
+      Target::Rust | Target::Python => Naming::SnakeCase,
+      Target::Go => Naming::PascalCase,
+    }
+  }
+}
+
+/// Splits a key into words at separators and case boundaries: `getUsers-userId` and
+/// `HTTPServer` become `get Users user Id` and `HTTP Server`.
+pub fn words(key: &str) -> Vec<String> {
+  let mut words = vec![];
+  for part in key.split(|c: char| !c.is_alphanumeric()) {
+    let chars = part.chars().collect::<Vec<_>>();
+    let mut word = String::new();
+    for (index, c) in chars.iter().enumerate() {
+      let previous = if index > 0 {
+        Some(chars[index - 1])
+      } else {
+        None
+      };
+      let next = chars.get(index + 1);
+      let boundary = c.is_uppercase()
+        && match previous {
+          Some(previous) if previous.is_lowercase() || previous.is_numeric() => true,
+          Some(previous) if previous.is_uppercase() => next.is_some_and(|n| n.is_lowercase()),
+          _ => false,
+        };
+      if boundary && !word.is_empty() {
+        words.push(word);
+        word = String::new();
+      }
+      word.push(*c);
+    }
+    if !word.is_empty() {
+      words.push(word);
+    }
+  }
+  words
+}
+
+/// Uppercases the first character; safe for empty and non-ASCII strings.
+pub fn head_uppercase(str: &str) -> String {
+  let mut chars = str.chars();
+  match chars.next() {
+    Some(head) => head.to_uppercase().chain(chars).collect(),
+    None => String::new(),
+  }
+}
+
+pub fn pascal_case(key: &str) -> String {
+  words(key).iter().map(|word| head_uppercase(word)).collect()
+}
+
+pub fn camel_case(key: &str) -> String {
+  let words = words(key);
+  match words.split_first() {
+    Some((head, tail)) => {
+      head.to_lowercase()
+        + &tail
+          .iter()
+          .map(|word| head_uppercase(word))
+          .collect::<String>()
+    }
+    None => String::new(),
+  }
+}
+
+pub fn snake_case(key: &str) -> String {
+  words(key)
+    .iter()
+    .map(|word| word.to_lowercase())
+    .collect::<Vec<_>>()
+    .join("_")
+}
+
+/// PascalCase name for generated types, prefixed with `V` when it would not start with a
+/// letter (`200` becomes `V200`, an empty key `V`).
+pub fn type_name(key: &str) -> String {
+  let pascal = pascal_case(key);
+  match pascal.chars().next() {
+    Some(head) if head.is_alphabetic() => pascal,
+    _ => format!("V{}", pascal),
+  }
+}
+
+/// Suffixes names colliding with an earlier one with a number: `AB, AB` becomes `AB, AB2`.
+pub fn unique(names: Vec<String>) -> Vec<String> {
+  let mut uniques: Vec<String> = vec![];
+  for name in names {
+    let mut unique = name.clone();
+    let mut index = 2;
+    while uniques.contains(&unique) {
+      unique = format!("{}{}", name, index);
+      index += 1;
+    }
+    uniques.push(unique);
+  }
+  uniques
+}
+
+/// Type names for enum values, `a-b` and `a_b` both become `AB` so later ones get a numeric
+/// suffix (`AB2`).
+pub fn variant_names(values: &[String]) -> Vec<String> {
+  unique(values.iter().map(|value| type_name(value)).collect())
+}
+
+fn is_identifier(name: &str) -> bool {
+  let mut chars = name.chars();
+  match chars.next() {
+    Some(head) if head.is_alphabetic() || head == '_' || head == '$' => {
+      chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    }
+    _ => false,
+  }
+}
+
+pub fn is_reserved(name: &str, target: Target) -> bool {
+  let reserved: &[&str] = match target {
+    Target::Scala => &[
+      "abstract",
+      "case",
+      "catch",
+      "class",
+      "def",
+      "do",
+      "else",
+      "extends",
+      "false",
+      "final",
+      "finally",
+      "for",
+      "forSome",
+      "if",
+      "implicit",
+      "import",
+      "lazy",
+      "match",
+      "new",
+      "null",
+      "object",
+      "override",
+      "package",
+      "private",
+      "protected",
+      "return",
+      "sealed",
+      "super",
+      "this",
+      "throw",
+      "trait",
+      "true",
+      "try",
+      "type",
+      "val",
+      "var",
+      "while",
+      "with",
+      "yield",
+      "given",
+      "enum",
+      "export",
+      "then",
+    ],
+    // any identifier is a valid property name in TypeScript
+    Target::TypeScript => &[],
+    Target::Rust => &[
+      "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum", "extern",
+      "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+      "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use",
+      "where", "while", "yield",
+    ],
+    Target::Python => &[
+      "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+      "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if",
+      "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try",
+      "while", "with", "yield",
+    ],
+    // exported Go fields start with an uppercase letter and never clash with keywords
+    Target::Go => &[],
+    Target::Java => &[
+      "abstract",
+      "assert",
+      "boolean",
+      "break",
+      "byte",
+      "case",
+      "catch",
+      "char",
+      "class",
+      "const",
+      "continue",
+      "default",
+      "do",
+      "double",
+      "else",
+      "enum",
+      "extends",
+      "final",
+      "finally",
+      "float",
+      "for",
+      "goto",
+      "if",
+      "implements",
+      "import",
+      "instanceof",
+      "int",
+      "interface",
+      "long",
+      "native",
+      "new",
+      "package",
+      "private",
+      "protected",
+      "public",
+      "return",
+      "short",
+      "static",
+      "strictfp",
+      "super",
+      "switch",
+      "synchronized",
+      "this",
+      "throw",
+      "throws",
+      "transient",
+      "try",
+      "void",
+      "volatile",
+      "while",
+      "record",
+      "var",
+      "yield",
+    ],
+    Target::Elm => &[
+      "if", "then", "else", "case", "of", "let", "in", "type", "module", "where", "import",
+      "exposing", "as", "port", "alias",
+    ],
+  };
+  reserved.contains(&name)
+}
+
+/// Makes `name` usable as an identifier of `target`: Scala backticks, TypeScript quotes,
+/// Rust raw identifiers and a trailing `_` elsewhere.
+pub fn escape(name: &str, target: Target) -> String {
+  let valid = is_identifier(name) && !name.contains('$');
+  match target {
+    Target::Scala if !valid || is_reserved(name, target) => format!("`{}`", name),
+    Target::TypeScript if !valid => format!("{:?}", name),
+    Target::Rust if is_reserved(name, target) => format!("r#{}", name),
+    _ if is_reserved(name, target) => format!("{}_", name),
+    _ => name.to_string(),
+  }
+}
+
+/// Field identifier for a JSON key in the given `naming`, by default the target's convention.
+/// Generators map a converted name back to the key with their serialization annotations
+/// (`(__ \ "key")`, `#[serde(rename)]`, `Field(alias=)`, `@JsonProperty`, json tags, ...).
+pub fn field_name(key: &str, target: Target, naming: Option<Naming>) -> String {
+  let name = match naming.unwrap_or_else(|| Naming::default_for(target)) {
+    Naming::Key if target == Target::Scala || target == Target::TypeScript => key.to_string(),
+    // the other targets cannot quote an identifier
+    Naming::Key => key
+      .chars()
+      .map(|c| if c.is_alphanumeric() { c } else { '_' })
+      .collect(),
+    Naming::CamelCase => camel_case(key),
+    Naming::SnakeCase => snake_case(key),
+    Naming::PascalCase => pascal_case(key),
+  };
+  let name = match name.chars().next() {
+    Some(head) if head.is_numeric() && target == Target::Go => format!("V{}", name),
+    Some(head) if head.is_numeric() && target != Target::TypeScript => format!("_{}", name),
+    None if target == Target::Go => "V".to_string(),
+    None => "_".to_string(),
+    _ => name,
+  };
+  // Go only serializes exported fields, and Elm record fields start in lowercase
+  let name = match target {
+    Target::Go => head_uppercase(&name),
+    Target::Elm => {
+      let mut chars = name.chars();
+      chars
+        .next()
+        .map(|head| head.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+    }
+    _ => name,
+  };
+  escape(&name, target)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_naming() {
+    assert_eq!(
+      vec!["get", "users", "user", "Id"],
+      words("get-users-userId")
+    );
+    assert_eq!(vec!["HTTP", "Server", "2"], words("HTTPServer_2"));
+    assert_eq!("barAt", camel_case("bar_at"));
+    assert_eq!("GetUsersUserId", pascal_case("get-users-userId"));
+    assert_eq!("hoge_id", snake_case("hogeId"));
+    assert_eq!("", head_uppercase(""));
+    assert_eq!("Éa", head_uppercase("éa"));
+    assert_eq!("名前", type_name("名前"));
+    assert_eq!("V200", type_name("200"));
+
+    assert_eq!("`type`", field_name("type", Target::Scala, None));
+    assert_eq!("fooBar", field_name("foo-bar", Target::Scala, None));
+    assert_eq!(
+      "`foo-bar`",
+      field_name("foo-bar", Target::Scala, Some(Naming::Key))
+    );
+    assert_eq!(
+      "\"foo-bar\"",
+      field_name("foo-bar", Target::TypeScript, None)
+    );
+    assert_eq!(
+      "fooBar",
+      field_name("foo-bar", Target::TypeScript, Some(Naming::CamelCase))
+    );
+    assert_eq!("type", field_name("type", Target::TypeScript, None));
+    assert_eq!("r#type", field_name("type", Target::Rust, None));
+    assert_eq!("class_", field_name("class", Target::Python, None));
+    assert_eq!("fooBar", field_name("foo-bar", Target::Java, None));
+    assert_eq!("_1st", field_name("1st", Target::Python, None));
+    assert_eq!("FooBar", field_name("foo_bar", Target::Go, None));
+    assert_eq!("V1st", field_name("1st", Target::Go, None));
+    assert_eq!(
+      "Foo_bar",
+      field_name("foo-bar", Target::Go, Some(Naming::Key))
+    );
+    assert_eq!("_", field_name("", Target::Elm, None));
+    assert_eq!(
+      "fooBar",
+      field_name("foo_bar", Target::Elm, Some(Naming::PascalCase))
+    );
+    assert_eq!(Some(Naming::SnakeCase), Naming::parse("snake_case"));
+  }
+}
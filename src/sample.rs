@@ -0,0 +1,335 @@
+use crate::apis::{self, Content, Method};
+use yaml_rust::yaml::Hash;
+use yaml_rust::Yaml;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Options {
+  pub seed: u64,
+  /// Emit `null` for nullable properties instead of a value.
+  pub nulls: bool,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Options {
+      seed: 42,
+      nulls: false,
+    }
+  }
+}
+
+// xorshift64*, enough for stable fake data without pulling in a rand crate
+struct Rng(u64);
+
+impl Rng {
+  fn new(seed: u64, salt: &str) -> Rng {
+    // FNV-1a of the salt keeps each operation's data independent of generation order
+    let hash = salt.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+      (hash ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3)
+    });
+    Rng((seed ^ hash).max(1))
+  }
+
+  fn next(&mut self, bound: u64) -> u64 {
+    self.0 ^= self.0 >> 12;
+    self.0 ^= self.0 << 25;
+    self.0 ^= self.0 >> 27;
+    self.0.wrapping_mul(0x2545_f491_4f6c_dd1d) % bound
+  }
+}
+
+/// `example` of a media type object, the first of its `examples`, or the schema's `example`.
+pub fn media_example(media: &Yaml) -> Option<Yaml> {
+  if !media["example"].is_badvalue() {
+    return Some(media["example"].clone());
+  }
+  if let Some(examples) = media["examples"].as_hash() {
+    if let Some((_, example)) = examples.iter().next() {
+      return Some(example["value"].clone());
+    }
+  }
+  schema_example(&media["schema"])
+}
+
+fn schema_example(schema: &Yaml) -> Option<Yaml> {
+  if !schema["example"].is_badvalue() {
+    Some(schema["example"].clone())
+  } else if let Some(example) = schema["examples"].as_vec().and_then(|e| e.first()) {
+    Some(example.clone())
+  } else if !schema["default"].is_badvalue() {
+    Some(schema["default"].clone())
+  } else {
+    None
+  }
+}
+
+/// Walks `content` alongside the schema it was parsed from, preferring the schema's
+/// `example`/`default` and falling back to fake data drawn from the seeded generator.
+fn sample_value(
+  content: &Content,
+  schema: &Yaml,
+  key: &str,
+  rng: &mut Rng,
+  options: Options,
+) -> Yaml {
+  if let Some(example) = schema_example(schema) {
+    return example;
+  }
+  match content {
+    Content::Object(properties) => {
+      let mut hash = Hash::new();
+      for property in properties {
+        let value = if property.or_null && options.nulls {
+          Yaml::Null
+        } else {
+          sample_value(
+            &property.value,
+            &schema["properties"][&property.key[..]],
+            &property.key,
+            rng,
+            options,
+          )
+        };
+        hash.insert(Yaml::String(property.key.clone()), value);
+      }
+      Yaml::Hash(hash)
+    }
+    Content::Array(items) => Yaml::Array(vec![sample_value(
+      items,
+      &schema["items"],
+      key,
+      rng,
+      options,
+    )]),
+    Content::Tuple(items) => Yaml::Array(
+      items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+          let item_schema = match schema["prefixItems"].as_vec() {
+            Some(schemas) if index < schemas.len() => schemas[index].clone(),
+            _ => Yaml::BadValue,
+          };
+          sample_value(item, &item_schema, key, rng, options)
+        })
+        .collect(),
+    ),
+    Content::Union(contents) => match contents.first() {
+      Some(content) => sample_value(content, schema, key, rng, options),
+      None => Yaml::Null,
+    },
+    Content::Enum(values) if !values.is_empty() => {
+      Yaml::String(values[rng.next(values.len() as u64) as usize].clone())
+    }
+    Content::Enum(_) => Yaml::Null,
+    Content::Const(value) => value.clone(),
+    Content::String => Yaml::String(format!(
+      "{}-{}",
+      if key.is_empty() { "string" } else { key },
+      rng.next(1000)
+    )),
+    Content::Binary => Yaml::String("".to_string()),
+    Content::Date => Yaml::String(format!(
+      "{}-{:02}-{:02}",
+      2000 + rng.next(30),
+      1 + rng.next(12),
+      1 + rng.next(28)
+    )),
+    Content::Integer => Yaml::Integer(rng.next(1000) as i64),
+    Content::Number => {
+      let cents = rng.next(100_000);
+      Yaml::Real(format!("{}.{:02}", cents / 100, cents % 100))
+    }
+    Content::Boolean => Yaml::Boolean(rng.next(2) == 1),
+  }
+}
+
+fn operation(document: &Yaml, path: &str, method_type: &str) -> Yaml {
+  document["paths"][path][method_type].clone()
+}
+
+/// Example of the request body. `document` is the one the model was read from, in the
+/// OpenAPI 3 layout of `swagger2::as_openapi3`.
+pub fn command_example(
+  document: &Yaml,
+  path: &str,
+  method_type: &str,
+  method: &Method,
+  options: Options,
+) -> Option<Yaml> {
+  let content = method.request_body_opt.as_ref()?;
+  let media_type = method
+    .request_media_type
+    .clone()
+    .unwrap_or_else(|| "application/json".to_string());
+  let media = &operation(document, path, method_type)["requestBody"]["content"][&media_type[..]];
+  Some(media_example(media).unwrap_or_else(|| {
+    sample_value(
+      content,
+      &media["schema"],
+      "",
+      &mut Rng::new(options.seed, &format!("{}/command", method.operation_id)),
+      options,
+    )
+  }))
+}
+
+/// Example of the primary success response, with its status code.
+pub fn view_model_example(
+  document: &Yaml,
+  path: &str,
+  method_type: &str,
+  method: &Method,
+  options: Options,
+) -> Option<(String, Yaml)> {
+  let status = apis::primary_success_status(&method.response_map)?;
+  let body = method.response_map[&status].as_ref()?;
+  let media = &operation(document, path, method_type)["responses"][&status[..]]["content"]
+    [&body.media_type[..]];
+  let example = media_example(media).unwrap_or_else(|| {
+    sample_value(
+      &body.content,
+      &media["schema"],
+      "",
+      &mut Rng::new(options.seed, &format!("{}/viewmodel", method.operation_id)),
+      options,
+    )
+  });
+  Some((status, example))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::apis::from_yaml;
+  use crate::loader;
+  use regex::Regex;
+  use yaml_rust::YamlLoader;
+
+  #[test]
+  fn it_examples() {
+    let yaml = "
+      paths:
+        /users:
+          post:
+            summary: ユーザ登録
+            operationId: post-users
+            requestBody:
+              content:
+                application/json:
+                  schema:
+                    type: object
+                    properties:
+                      name:
+                        type: string
+                        example: abab
+                      role:
+                        type: string
+                        enum:
+                          - admin
+                      birthday:
+                        type: string
+                        format: date
+                      nickname:
+                        type: string
+                        nullable: true
+                        default: nick
+                      tags:
+                        type: array
+                        items:
+                          type: integer
+            responses:
+              '201':
+                content:
+                  application/json:
+                    schema:
+                      type: object
+                      properties:
+                        id:
+                          type: integer
+                    examples:
+                      created:
+                        value:
+                          id: 1
+      ";
+    let document = &YamlLoader::load_from_str(yaml).unwrap()[0];
+    let apis = from_yaml(document);
+    let method = &apis[0].method_map["post"];
+
+    let command = command_example(document, "/users", "post", method, Options::default());
+    let command = loader::to_json(&command.unwrap());
+    assert!(command.contains("\"name\": \"abab\""));
+    assert!(command.contains("\"role\": \"admin\""));
+    assert!(command.contains("\"nickname\": \"nick\""));
+    assert!(Regex::new(r#""birthday": "\d{4}-\d{2}-\d{2}""#)
+      .unwrap()
+      .is_match(&command));
+    assert_eq!(
+      Some(command),
+      command_example(document, "/users", "post", method, Options::default())
+        .map(|command| loader::to_json(&command))
+    );
+
+    let nulls = Options {
+      seed: 42,
+      nulls: true,
+    };
+    let command = command_example(document, "/users", "post", method, nulls).unwrap();
+    assert_eq!(Yaml::Null, command["nickname"]);
+
+    assert_eq!(
+      Some((
+        "201".to_string(),
+        YamlLoader::load_from_str("id: 1").unwrap()[0].clone()
+      )),
+      view_model_example(document, "/users", "post", method, Options::default())
+    );
+  }
+
+  #[test]
+  fn it_examples_from_swagger2() {
+    let yaml = "
+      swagger: '2.0'
+      paths:
+        /users:
+          post:
+            summary: ユーザ登録
+            operationId: post-users
+            parameters:
+              - name: body
+                in: body
+                schema:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                      example: abab
+            responses:
+              '201':
+                description: Created
+                schema:
+                  type: object
+                  properties:
+                    id:
+                      type: integer
+                  example:
+                    id: 1
+      ";
+    let document = &crate::swagger2::as_openapi3(&YamlLoader::load_from_str(yaml).unwrap()[0]);
+    let apis = from_yaml(document);
+    let method = &apis[0].method_map["post"];
+
+    let command = command_example(document, "/users", "post", method, Options::default());
+    assert_eq!(
+      Some("{\n  \"name\": \"abab\"\n}".to_string()),
+      command.map(|command| loader::to_json(&command))
+    );
+    assert_eq!(
+      Some((
+        "201".to_string(),
+        YamlLoader::load_from_str("id: 1").unwrap()[0].clone()
+      )),
+      view_model_example(document, "/users", "post", method, Options::default())
+    );
+  }
+}
@@ -0,0 +1,491 @@
+use yaml_rust::yaml::Hash;
+use yaml_rust::Yaml;
+
+fn key(str: &str) -> Yaml {
+  Yaml::String(str.to_string())
+}
+
+fn hash(entries: Vec<(&str, Yaml)>) -> Yaml {
+  let mut hash = Hash::new();
+  for (name, value) in entries {
+    hash.insert(key(name), value);
+  }
+  Yaml::Hash(hash)
+}
+
+// 2.0 marks nullable schemas with the `x-nullable` extension where 3.0 has `nullable`
+fn to_schema_object(schema: &Yaml) -> Yaml {
+  match schema {
+    Yaml::Hash(entries) => Yaml::Hash(
+      entries
+        .iter()
+        .map(|(name, value)| match name.as_str() {
+          Some("x-nullable") => (key("nullable"), value.clone()),
+          // values, not schemas
+          Some("example") | Some("default") | Some("enum") => (name.clone(), value.clone()),
+          // names, not keywords
+          Some("properties") => (
+            name.clone(),
+            Yaml::Hash(
+              value
+                .as_hash()
+                .into_iter()
+                .flatten()
+                .map(|(property, schema)| (property.clone(), to_schema_object(schema)))
+                .collect(),
+            ),
+          ),
+          _ => (name.clone(), to_schema_object(value)),
+        })
+        .collect(),
+    ),
+    Yaml::Array(items) => Yaml::Array(items.iter().map(to_schema_object).collect()),
+    _ => schema.clone(),
+  }
+}
+
+fn content(media_types: &[Yaml], schema: &Yaml) -> Yaml {
+  let mut content = Hash::new();
+  for media_type in media_types {
+    content.insert(
+      media_type.clone(),
+      hash(vec![("schema", to_schema_object(schema))]),
+    );
+  }
+  Yaml::Hash(content)
+}
+
+fn copy(from: &Yaml, names: &[&str], to: &mut Hash) {
+  for name in names {
+    if !from[*name].is_badvalue() {
+      to.insert(key(name), from[*name].clone());
+    }
+  }
+}
+
+// `type: file` only exists in 2.0, 3 describes uploads as binary strings
+fn to_schema(parameter: &Yaml) -> Yaml {
+  let mut schema = Hash::new();
+  if parameter["type"].as_str() == Some("file") {
+    schema.insert(key("type"), key("string"));
+    schema.insert(key("format"), key("binary"));
+  } else {
+    copy(
+      parameter,
+      &[
+        "type",
+        "format",
+        "enum",
+        "items",
+        "default",
+        "minimum",
+        "maximum",
+        "minLength",
+        "maxLength",
+        "pattern",
+        "x-nullable",
+      ],
+      &mut schema,
+    );
+  }
+  copy(parameter, &["description"], &mut schema);
+  to_schema_object(&Yaml::Hash(schema))
+}
+
+fn to_parameter(parameter: &Yaml) -> Yaml {
+  let mut converted = Hash::new();
+  copy(
+    parameter,
+    &["name", "in", "description", "required"],
+    &mut converted,
+  );
+  let mut schema = to_schema(parameter).into_hash().unwrap_or_default();
+  schema.remove(&key("description"));
+  converted.insert(key("schema"), Yaml::Hash(schema));
+  Yaml::Hash(converted)
+}
+
+// form fields become the properties of one object schema, as 3 models them
+fn to_form_body(parameters: &[&Yaml], consumes: &[Yaml]) -> Yaml {
+  let mut properties = Hash::new();
+  let mut required = vec![];
+  for parameter in parameters {
+    properties.insert(parameter["name"].clone(), to_schema(parameter));
+    if parameter["required"].as_bool() == Some(true) {
+      required.push(parameter["name"].clone());
+    }
+  }
+  let mut media_types = consumes
+    .iter()
+    .filter(|media_type| {
+      media_type
+        .as_str()
+        .map(crate::apis::is_form_media_type)
+        .unwrap_or(false)
+    })
+    .cloned()
+    .collect::<Vec<_>>();
+  if media_types.is_empty() {
+    let upload = parameters
+      .iter()
+      .any(|parameter| parameter["type"].as_str() == Some("file"));
+    media_types.push(key(if upload {
+      "multipart/form-data"
+    } else {
+      "application/x-www-form-urlencoded"
+    }));
+  }
+  let mut schema = vec![
+    ("type", key("object")),
+    ("properties", Yaml::Hash(properties)),
+  ];
+  if !required.is_empty() {
+    schema.push(("required", Yaml::Array(required)));
+  }
+  hash(vec![("content", content(&media_types, &hash(schema)))])
+}
+
+fn to_responses(responses: &Yaml, produces: &[Yaml]) -> Yaml {
+  let mut converted = Hash::new();
+  for (status, response) in responses.as_hash().into_iter().flatten() {
+    let mut response_hash = response.as_hash().cloned().unwrap_or_else(Hash::new);
+    if let Some(schema) = response_hash.remove(&key("schema")) {
+      response_hash.insert(key("content"), content(produces, &schema));
+    }
+    converted.insert(status.clone(), Yaml::Hash(response_hash));
+  }
+  Yaml::Hash(converted)
+}
+
+// the operation's list replaces the global one, JSON when neither is given
+fn media_types(operation: &Yaml, document: &Yaml, name: &str) -> Vec<Yaml> {
+  operation[name]
+    .as_vec()
+    .or_else(|| document[name].as_vec())
+    .filter(|media_types| !media_types.is_empty())
+    .cloned()
+    .unwrap_or_else(|| vec![key("application/json")])
+}
+
+fn to_operation(operation: &Yaml, path_parameters: &[Yaml], document: &Yaml) -> Yaml {
+  let mut converted = operation.as_hash().cloned().unwrap_or_else(Hash::new);
+  let consumes = media_types(operation, document, "consumes");
+  let produces = media_types(operation, document, "produces");
+  converted.remove(&key("produces"));
+  converted.remove(&key("consumes"));
+  let operation_parameters = converted
+    .remove(&key("parameters"))
+    .and_then(|parameters| parameters.into_vec())
+    .unwrap_or_else(Vec::new);
+  // path level parameters apply unless the operation overrides them
+  let parameters = path_parameters
+    .iter()
+    .filter(|parameter| parameter["in"].as_str() != Some("path"))
+    .filter(|parameter| {
+      !operation_parameters.iter().any(|overriding| {
+        overriding["name"] == parameter["name"] && overriding["in"] == parameter["in"]
+      })
+    })
+    .chain(operation_parameters.iter())
+    .collect::<Vec<_>>();
+  let located = |location: &str| {
+    parameters
+      .iter()
+      .filter(|parameter| parameter["in"].as_str() == Some(location))
+      .cloned()
+      .collect::<Vec<_>>()
+  };
+
+  if let Some(body) = located("body").first() {
+    let mut request_body = vec![("content", content(&consumes, &body["schema"]))];
+    if body["required"].as_bool() == Some(true) {
+      request_body.push(("required", Yaml::Boolean(true)));
+    }
+    converted.insert(key("requestBody"), hash(request_body));
+  }
+  let form_parameters = located("formData");
+  if !form_parameters.is_empty() {
+    converted.insert(
+      key("requestBody"),
+      to_form_body(&form_parameters, &consumes),
+    );
+  }
+  let other_parameters = parameters
+    .iter()
+    .filter(|parameter| {
+      !matches!(
+        parameter["in"].as_str(),
+        Some("body") | Some("formData") | Some("path")
+      )
+    })
+    .map(|parameter| to_parameter(parameter))
+    .collect::<Vec<_>>();
+  if !other_parameters.is_empty() {
+    converted.insert(key("parameters"), Yaml::Array(other_parameters));
+  }
+  if let Some(responses) = converted.remove(&key("responses")) {
+    converted.insert(key("responses"), to_responses(&responses, &produces));
+  }
+  Yaml::Hash(converted)
+}
+
+/// The document in the OpenAPI 3 layout: Swagger 2.0 is converted, anything else is kept.
+pub fn as_openapi3(document: &Yaml) -> Yaml {
+  match document["swagger"].as_str() {
+    Some(version) if version.starts_with('2') => to_openapi3(document),
+    _ => document.clone(),
+  }
+}
+
+/// Converts a Swagger 2.0 document into the OpenAPI 3 layout read by `apis::from_yaml`.
+/// Path parameters declared on operations are hoisted to the path, as `from_yaml` expects,
+/// other path level parameters are copied into each operation.
+pub fn to_openapi3(document: &Yaml) -> Yaml {
+  let mut paths = Hash::new();
+  for (path, path_item) in document["paths"].as_hash().into_iter().flatten() {
+    let mut converted = Hash::new();
+    let path_item_parameters = path_item["parameters"]
+      .as_vec()
+      .cloned()
+      .unwrap_or_else(Vec::new);
+    let mut path_parameters = path_item_parameters.clone();
+    for (method, operation) in path_item.as_hash().into_iter().flatten() {
+      if method.as_str() == Some("parameters") {
+        continue;
+      }
+      path_parameters.extend(
+        operation["parameters"]
+          .as_vec()
+          .into_iter()
+          .flatten()
+          .filter(|parameter| parameter["in"].as_str() == Some("path"))
+          .cloned(),
+      );
+      converted.insert(
+        method.clone(),
+        to_operation(operation, &path_item_parameters, document),
+      );
+    }
+
+    let mut names = vec![];
+    let path_parameters = path_parameters
+      .into_iter()
+      .filter(|parameter| parameter["in"].as_str() == Some("path"))
+      .filter(|parameter| {
+        let name = parameter["name"].clone();
+        if names.contains(&name) {
+          false
+        } else {
+          names.push(name);
+          true
+        }
+      })
+      .map(|parameter| to_parameter(&parameter))
+      .collect::<Vec<_>>();
+    if !path_parameters.is_empty() {
+      converted.insert(key("parameters"), Yaml::Array(path_parameters));
+    }
+    paths.insert(path.clone(), Yaml::Hash(converted));
+  }
+
+  hash(vec![
+    ("openapi", key("3.0.0")),
+    ("info", document["info"].clone()),
+    ("paths", Yaml::Hash(paths)),
+    (
+      "components",
+      hash(vec![(
+        "schemas",
+        match document["definitions"] {
+          Yaml::BadValue => Yaml::Hash(Hash::new()),
+          ref definitions => to_schema_object(definitions),
+        },
+      )]),
+    ),
+  ])
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::apis::*;
+  use yaml_rust::YamlLoader;
+
+  #[test]
+  fn it_to_openapi3_media_types() {
+    let swagger = "
+      swagger: '2.0'
+      info:
+        title: local
+        version: '1.0'
+      consumes:
+        - application/json
+      produces:
+        - application/json
+      paths:
+        '/users/{userId}/avatar':
+          parameters:
+            - name: userId
+              in: path
+              required: true
+              type: integer
+            - name: X-Trace
+              in: header
+              type: string
+          put:
+            operationId: put-avatar
+            consumes:
+              - multipart/form-data
+            produces:
+              - image/png
+            parameters:
+              - name: file
+                in: formData
+                required: true
+                type: file
+              - name: caption
+                in: formData
+                type: string
+                x-nullable: true
+            responses:
+              '200':
+                description: OK
+                schema:
+                  type: string
+                  format: binary
+      ";
+    let openapi = "
+      openapi: 3.0.0
+      info:
+        title: local
+        version: '1.0'
+      paths:
+        '/users/{userId}/avatar':
+          put:
+            operationId: put-avatar
+            requestBody:
+              content:
+                multipart/form-data:
+                  schema:
+                    type: object
+                    properties:
+                      file:
+                        type: string
+                        format: binary
+                      caption:
+                        type: string
+                        nullable: true
+                    required:
+                      - file
+            parameters:
+              - name: X-Trace
+                in: header
+                schema:
+                  type: string
+            responses:
+              '200':
+                description: OK
+                content:
+                  image/png:
+                    schema:
+                      type: string
+                      format: binary
+          parameters:
+            - name: userId
+              in: path
+              required: true
+              schema:
+                type: integer
+      components:
+        schemas: {}
+      ";
+
+    assert_eq!(
+      YamlLoader::load_from_str(openapi).unwrap()[0],
+      crate::swagger2::to_openapi3(&YamlLoader::load_from_str(swagger).unwrap()[0])
+    );
+  }
+
+  #[test]
+  fn it_from_swagger2() {
+    let swagger = "
+      swagger: '2.0'
+      info:
+        title: local
+        version: '1.0'
+      produces:
+        - application/json
+      paths:
+        '/users/{userId}':
+          put:
+            summary: ユーザ詳細PUT
+            operationId: put-users-userId
+            parameters:
+              - name: userId
+                in: path
+                type: integer
+              - name: body
+                in: body
+                schema:
+                  type: object
+                  properties:
+                    name:
+                      type: string
+                      x-nullable: true
+            responses:
+              '200':
+                description: OK
+                schema:
+                  type: array
+                  items:
+                    type: boolean
+      definitions:
+        User:
+          type: object
+          properties:
+            x-nullable:
+              type: string
+              x-nullable: true
+      ";
+    let docs = YamlLoader::load_from_str(swagger).unwrap();
+
+    assert_eq!(
+      vec![Api {
+        path: "/users/{userId}".to_string(),
+        param_map: vec![("userId".to_string(), ParamType::Integer)]
+          .into_iter()
+          .collect(),
+        method_map: vec![(
+          "put".to_string(),
+          Method {
+            operation_id: "put-users-userId".to_string(),
+            summary: "ユーザ詳細PUT".to_string(),
+            response_opt: Some(Content::Array(Box::new(Content::Boolean))),
+            request_body_opt: Some(Content::Object(vec![Property {
+              or_null: true,
+              ..Property::new("name", Content::String)
+            }])),
+            request_media_type: Some("application/json".to_string()),
+            docs: Docs::default(),
+            response_map: vec![(
+              "200".to_string(),
+              Some(Body {
+                media_type: "application/json".to_string(),
+                content: Content::Array(Box::new(Content::Boolean)),
+              }),
+            )]
+            .into_iter()
+            .collect(),
+          },
+        )]
+        .into_iter()
+        .collect(),
+      }],
+      from_document(&docs[0])
+    );
+    assert_eq!(
+      YamlLoader::load_from_str("{type: string, nullable: true}").unwrap()[0],
+      crate::swagger2::to_openapi3(&docs[0])["components"]["schemas"]["User"]["properties"]
+        ["x-nullable"]
+    );
+  }
+}
@@ -0,0 +1,336 @@
+//! A small engine for user-defined output templates, implementing a limited subset of the
+//! Handlebars syntax rather than Handlebars itself; the `.hbs` files are only named after it.
+//!
+//! `{{name}}` and `{{a.b}}` insert values of the view, `{{#each list}}`, `{{#if value}}` and
+//! `{{#unless value}}` open blocks that may have an `{{else}}` and close with `{{/each}}`, ...
+//! Inside `each`, `this`, `@index`, `@first` and `@last` refer to the current item, and names not
+//! found on it are looked up in the outer views. `{{! ... }}` is a comment, and a `~` next to the
+//! braces (`{{~#if x~}}`) trims the whitespace on that side of the tag. Values are inserted
+//! verbatim since the output is code, not HTML.
+//!
+//! Nothing else is supported: helpers, arguments and hashes, partials (`{{> name}}`), `{{#with}}`,
+//! `{{else if}}`, `../` paths and `{{{{raw}}}}` blocks. Tags with arguments are rejected when the
+//! template is parsed. Braces in front of a tag are plain text, so `{{{name}}}` renders the value
+//! between literal braces instead of being the Handlebars unescaped insert.
+
+use yaml_rust::yaml::Hash;
+use yaml_rust::Yaml;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+enum Block {
+  Each,
+  If,
+  Unless,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+enum Node {
+  Text(String),
+  Value(String),
+  Block {
+    block: Block,
+    path: String,
+    body: Vec<Node>,
+    otherwise: Vec<Node>,
+  },
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Template {
+  nodes: Vec<Node>,
+}
+
+// a block being parsed: its opening tag, and whether `{{else}}` has been seen
+struct Open {
+  block: Block,
+  path: String,
+  line: usize,
+  body: Vec<Node>,
+  otherwise: Option<Vec<Node>>,
+}
+
+struct Frame {
+  value: Yaml,
+  position: Option<(usize, usize)>,
+}
+
+/// Builds a view object from `(key, value)` pairs, keeping their order.
+pub fn object(entries: Vec<(&str, Yaml)>) -> Yaml {
+  let mut hash = Hash::new();
+  for (key, value) in entries {
+    hash.insert(Yaml::String(key.to_string()), value);
+  }
+  Yaml::Hash(hash)
+}
+
+pub fn string(str: &str) -> Yaml {
+  Yaml::String(str.to_string())
+}
+
+fn is_truthy(value: &Yaml) -> bool {
+  match value {
+    Yaml::BadValue | Yaml::Null => false,
+    Yaml::Boolean(value) => *value,
+    Yaml::String(value) => !value.is_empty(),
+    Yaml::Integer(value) => *value != 0,
+    Yaml::Array(values) => !values.is_empty(),
+    Yaml::Hash(hash) => !hash.is_empty(),
+    _ => true,
+  }
+}
+
+fn push(stack: &mut [Open], nodes: &mut Vec<Node>, node: Node) {
+  match stack.last_mut() {
+    Some(Open {
+      otherwise: Some(otherwise),
+      ..
+    }) => otherwise.push(node),
+    Some(open) => open.body.push(node),
+    None => nodes.push(node),
+  }
+}
+
+impl Template {
+  pub fn parse(source: &str) -> Result<Template, String> {
+    let mut nodes = vec![];
+    let mut stack: Vec<Open> = vec![];
+    let mut rest = source;
+    let mut line = 1;
+    let mut trim_next = false;
+    loop {
+      // braces in front of a tag, like the `{` of `type A={{{#each ...`, are text
+      let start = rest
+        .find("{{")
+        .map(|start| start + rest[start + 2..].chars().take_while(|c| *c == '{').count());
+      let (text, tag) = match start {
+        Some(start) => (&rest[..start], Some(&rest[start + 2..])),
+        None => (rest, None),
+      };
+      let mut text = if trim_next { text.trim_start() } else { text };
+      let tag = match tag {
+        Some(tag) => {
+          let end = tag
+            .find("}}")
+            .ok_or_else(|| format!("line {}: unclosed tag", line))?;
+          line += rest[..rest.len() - tag.len()].matches('\n').count();
+          rest = &tag[end + 2..];
+          Some(&tag[..end])
+        }
+        None => None,
+      };
+      if let Some(tag) = tag {
+        if tag.starts_with('~') {
+          text = text.trim_end();
+        }
+      }
+      if !text.is_empty() {
+        push(&mut stack, &mut nodes, Node::Text(text.to_string()));
+      }
+      let tag = match tag {
+        Some(tag) => tag,
+        None => break,
+      };
+      line += tag.matches('\n').count();
+      trim_next = tag.ends_with('~');
+      let tag = tag.trim_start_matches('~').trim_end_matches('~').trim();
+      if tag.starts_with('!') {
+        continue;
+      }
+      let mut words = tag.split_whitespace();
+      let name = words.next().unwrap_or("");
+      let path = words.next().unwrap_or("").to_string();
+      if let Some(extra) = words.next() {
+        return Err(format!(
+          "line {}: unexpected '{}' in {{{{{}}}}}",
+          line, extra, tag
+        ));
+      }
+      let block = match name {
+        "#each" => Some(Block::Each),
+        "#if" => Some(Block::If),
+        "#unless" => Some(Block::Unless),
+        _ => None,
+      };
+      if let Some(block) = block {
+        if path.is_empty() {
+          return Err(format!("line {}: {} needs a value", line, name));
+        }
+        stack.push(Open {
+          block,
+          path,
+          line,
+          body: vec![],
+          otherwise: None,
+        });
+      } else if name == "else" {
+        match stack.last_mut() {
+          Some(open) if open.otherwise.is_none() => open.otherwise = Some(vec![]),
+          _ => return Err(format!("line {}: unexpected {{{{else}}}}", line)),
+        }
+      } else if let Some(closed) = name.strip_prefix('/') {
+        let open = stack
+          .pop()
+          .ok_or_else(|| format!("line {}: unexpected {{{{{}}}}}", line, tag))?;
+        let expected = match open.block {
+          Block::Each => "each",
+          Block::If => "if",
+          Block::Unless => "unless",
+        };
+        if closed != expected {
+          return Err(format!(
+            "line {}: {{{{{}}}}} closes {{{{#{}}}}} of line {}",
+            line, tag, expected, open.line
+          ));
+        }
+        let node = Node::Block {
+          block: open.block,
+          path: open.path,
+          body: open.body,
+          otherwise: open.otherwise.unwrap_or_default(),
+        };
+        push(&mut stack, &mut nodes, node);
+      } else if name.starts_with('#') || name.is_empty() || !path.is_empty() {
+        return Err(format!("line {}: unknown tag {{{{{}}}}}", line, tag));
+      } else {
+        push(&mut stack, &mut nodes, Node::Value(name.to_string()));
+      }
+    }
+    match stack.pop() {
+      Some(open) => Err(format!("line {}: {:?} is not closed", open.line, open.path)),
+      None => Ok(Template { nodes }),
+    }
+  }
+
+  pub fn render(&self, view: &Yaml) -> String {
+    let mut out = String::new();
+    render_nodes(
+      &self.nodes,
+      &mut vec![Frame {
+        value: view.clone(),
+        position: None,
+      }],
+      &mut out,
+    );
+    out
+  }
+}
+
+fn lookup(frames: &[Frame], path: &str) -> Yaml {
+  if let Some(variable) = path.strip_prefix('@') {
+    let (index, len) = match frames.iter().rev().find_map(|frame| frame.position) {
+      Some(position) => position,
+      None => return Yaml::BadValue,
+    };
+    return match variable {
+      "index" => Yaml::Integer(index as i64),
+      "first" => Yaml::Boolean(index == 0),
+      "last" => Yaml::Boolean(index + 1 == len),
+      _ => Yaml::BadValue,
+    };
+  }
+  let mut segments = path.split('.');
+  let head = segments.next().unwrap_or("");
+  let mut value = if head == "this" {
+    frames.last().map(|frame| &frame.value)
+  } else {
+    frames
+      .iter()
+      .rev()
+      .map(|frame| &frame.value[head])
+      .find(|value| !value.is_badvalue())
+  };
+  for segment in segments {
+    value = value.map(|value| &value[segment]);
+  }
+  value.cloned().unwrap_or(Yaml::BadValue)
+}
+
+fn render_nodes(nodes: &[Node], frames: &mut Vec<Frame>, out: &mut String) {
+  for node in nodes {
+    match node {
+      Node::Text(text) => out.push_str(text),
+      Node::Value(path) => match lookup(frames, path) {
+        Yaml::String(value) | Yaml::Real(value) => out.push_str(&value),
+        Yaml::Integer(value) => out.push_str(&value.to_string()),
+        Yaml::Boolean(value) => out.push_str(&value.to_string()),
+        _ => {}
+      },
+      Node::Block {
+        block,
+        path,
+        body,
+        otherwise,
+      } => {
+        let value = lookup(frames, path);
+        match block {
+          Block::Each => match value {
+            Yaml::Array(items) if !items.is_empty() => {
+              let len = items.len();
+              for (index, item) in items.into_iter().enumerate() {
+                frames.push(Frame {
+                  value: item,
+                  position: Some((index, len)),
+                });
+                render_nodes(body, frames, out);
+                frames.pop();
+              }
+            }
+            _ => render_nodes(otherwise, frames, out),
+          },
+          Block::If | Block::Unless => {
+            if is_truthy(&value) == (*block == Block::If) {
+              render_nodes(body, frames, out)
+            } else {
+              render_nodes(otherwise, frames, out)
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_render_template() {
+    let view = object(vec![
+      ("name", string("Command")),
+      (
+        "fields",
+        Yaml::Array(vec![
+          object(vec![
+            ("key", string("id")),
+            ("optional", Yaml::Boolean(false)),
+          ]),
+          object(vec![
+            ("key", string("nickname")),
+            ("optional", Yaml::Boolean(true)),
+          ]),
+        ]),
+      ),
+      ("empty", Yaml::Array(vec![])),
+    ]);
+    let template = Template::parse(
+      "type {{name}}={{{#each fields}}{{! fields }}{{key}}{{#if optional}}?{{/if}}: {{@index}}{{#unless @last}}; {{/unless}}{{/each}}}
+{{#each empty}}x{{else}}none of {{name}}{{/each}}
+{{~#if missing}}!{{else}} ok {{~/if}}",
+    )
+    .unwrap();
+    assert_eq!(
+      "type Command={id: 0; nickname?: 1}\nnone of Command ok",
+      template.render(&view)
+    );
+
+    assert_eq!(
+      Err("line 2: {{/if}} closes {{#each}} of line 1".to_string()),
+      Template::parse("{{#each fields}}\n{{/if}}")
+    );
+    assert_eq!(
+      Err("line 1: \"x\" is not closed".to_string()),
+      Template::parse("{{#if x}}")
+    );
+  }
+}
@@ -0,0 +1,259 @@
+use crate::apis::{Api, Content, Method};
+use regex::Regex;
+use std::fmt;
+use yaml_rust::Yaml;
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct Mismatch {
+  pub path: String,
+  pub message: String,
+}
+
+impl fmt::Display for Mismatch {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}: {}", self.path, self.message)
+  }
+}
+
+pub fn find_operation<'a>(apis: &'a [Api], operation_id: &str) -> Option<&'a Method> {
+  apis
+    .iter()
+    .flat_map(|api| api.method_map.values())
+    .find(|method| method.operation_id == operation_id)
+}
+
+fn type_name(value: &Yaml) -> &str {
+  match value {
+    Yaml::Hash(_) => "object",
+    Yaml::Array(_) => "array",
+    Yaml::String(_) => "string",
+    Yaml::Integer(_) => "integer",
+    Yaml::Real(_) => "number",
+    Yaml::Boolean(_) => "boolean",
+    Yaml::Null => "null",
+    _ => "unknown",
+  }
+}
+
+// compiled once per validation instead of once per value
+struct Patterns {
+  identifier: Regex,
+  date: Regex,
+}
+
+fn child_path(patterns: &Patterns, path: &str, key: &str) -> String {
+  if patterns.identifier.is_match(key) {
+    format!("{}.{}", path, key)
+  } else {
+    format!("{}['{}']", path, key.replace('\'', "\\'"))
+  }
+}
+
+/// Checks a JSON document against a `Content` tree and reports every mismatch with its JSON
+/// path.
+pub fn validate(value: &Yaml, content: &Content) -> Vec<Mismatch> {
+  let patterns = Patterns {
+    identifier: Regex::new(r"^[A-Za-z_$][A-Za-z0-9_$]*$").unwrap(),
+    date: Regex::new(r"^\d{4}-\d{2}-\d{2}$").unwrap(),
+  };
+  let mut mismatches = vec![];
+  validate_value(&patterns, value, content, "$", &mut mismatches);
+  mismatches
+}
+
+fn validate_value(
+  patterns: &Patterns,
+  value: &Yaml,
+  content: &Content,
+  path: &str,
+  mismatches: &mut Vec<Mismatch>,
+) {
+  let mut mismatch = |message: String| {
+    mismatches.push(Mismatch {
+      path: path.to_string(),
+      message,
+    })
+  };
+  let expected = |expected: &str| format!("expected {}, found {}", expected, type_name(value));
+
+  match (content, value) {
+    (_, Yaml::Null) => mismatch("unexpected null".to_string()),
+    (Content::Object(properties), Yaml::Hash(hash)) => {
+      for property in properties {
+        let property_path = child_path(patterns, path, &property.key);
+        match hash.get(&Yaml::String(property.key.clone())) {
+          None if !property.required => {}
+          None => mismatches.push(Mismatch {
+            path: property_path,
+            message: "missing required property".to_string(),
+          }),
+          Some(Yaml::Null) if property.or_null => {}
+          Some(property_value) => validate_value(
+            patterns,
+            property_value,
+            &property.value,
+            &property_path,
+            mismatches,
+          ),
+        }
+      }
+    }
+    (Content::Object(_), _) => mismatch(expected("object")),
+    (Content::Array(items), Yaml::Array(values)) => {
+      for (index, item) in values.iter().enumerate() {
+        validate_value(
+          patterns,
+          item,
+          items,
+          &format!("{}[{}]", path, index),
+          mismatches,
+        );
+      }
+    }
+    (Content::Array(_), _) => mismatch(expected("array")),
+    (Content::Tuple(items), Yaml::Array(values)) => {
+      if items.len() != values.len() {
+        mismatch(format!(
+          "expected {} items, found {}",
+          items.len(),
+          values.len()
+        ));
+      }
+      for (index, (item, value)) in items.iter().zip(values.iter()).enumerate() {
+        validate_value(
+          patterns,
+          value,
+          item,
+          &format!("{}[{}]", path, index),
+          mismatches,
+        );
+      }
+    }
+    (Content::Tuple(_), _) => mismatch(expected("array")),
+    (Content::Union(contents), value) => {
+      if !contents.iter().any(|content| {
+        let mut member_mismatches = vec![];
+        validate_value(patterns, value, content, path, &mut member_mismatches);
+        member_mismatches.is_empty()
+      }) {
+        mismatch(format!(
+          "{} does not match any of the union types",
+          type_name(value)
+        ));
+      }
+    }
+    (Content::String, Yaml::String(_)) | (Content::Binary, Yaml::String(_)) => {}
+    (Content::String, _) | (Content::Binary, _) => mismatch(expected("string")),
+    (Content::Date, Yaml::String(date)) => {
+      if !patterns.date.is_match(date) {
+        mismatch(format!("'{}' is not a date (YYYY-MM-DD)", date));
+      }
+    }
+    (Content::Date, _) => mismatch(expected("date string")),
+    (Content::Enum(values), Yaml::String(string)) => {
+      if !values.contains(string) {
+        mismatch(format!(
+          "unknown enum value '{}', expected one of {}",
+          string,
+          values.join(", ")
+        ));
+      }
+    }
+    (Content::Enum(_), _) => mismatch(expected("enum string")),
+    (Content::Const(expected_value), value) => {
+      // 1 and 1.0 are the same JSON number
+      let number = |value: &Yaml| match value {
+        Yaml::Integer(integer) => Some(*integer as f64),
+        Yaml::Real(real) => real.parse::<f64>().ok(),
+        _ => None,
+      };
+      let same = match (number(expected_value), number(value)) {
+        (Some(expected_number), Some(number)) => expected_number == number,
+        _ => expected_value == value,
+      };
+      if !same {
+        mismatch(format!(
+          "expected {}, found {}",
+          crate::loader::to_json(expected_value),
+          crate::loader::to_json(value)
+        ));
+      }
+    }
+    (Content::Integer, Yaml::Integer(_)) => {}
+    (Content::Integer, _) => mismatch(expected("integer")),
+    (Content::Number, Yaml::Integer(_)) | (Content::Number, Yaml::Real(_)) => {}
+    (Content::Number, _) => mismatch(expected("number")),
+    (Content::Boolean, Yaml::Boolean(_)) => {}
+    (Content::Boolean, _) => mismatch(expected("boolean")),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::apis::Property;
+  use crate::loader;
+
+  #[test]
+  fn it_validate() {
+    let content = Content::Object(vec![
+      Property {
+        required: true,
+        ..Property::new("name", Content::String)
+      },
+      Property {
+        or_null: true,
+        ..Property::new("age", Content::Integer)
+      },
+      Property {
+        required: true,
+        ..Property::new(
+          "role",
+          Content::Enum(vec!["admin".to_string(), "member".to_string()]),
+        )
+      },
+      Property {
+        required: true,
+        ..Property::new("birthday", Content::Date)
+      },
+      Property::new(
+        "x-tags",
+        Content::Array(Box::new(Content::Union(vec![
+          Content::String,
+          Content::Number,
+        ]))),
+      ),
+    ]);
+    let validate_json = |json: &str| {
+      validate(
+        &loader::load_from_str(json, loader::Format::Json).unwrap(),
+        &content,
+      )
+      .iter()
+      .map(|mismatch| mismatch.to_string())
+      .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      Vec::<String>::new(),
+      validate_json(
+        r#"{"name": "abab", "age": null, "role": "admin", "birthday": "2020-01-01", "x-tags": ["a", 1.5]}"#
+      )
+    );
+    assert_eq!(
+      Vec::<String>::new(),
+      validate_json(r#"{"name": "abab", "role": "admin", "birthday": "2020-01-01"}"#)
+    );
+    assert_eq!(
+      vec![
+        "$.name: unexpected null",
+        "$.age: expected integer, found string",
+        "$.role: unknown enum value 'owner', expected one of admin, member",
+        "$.birthday: missing required property",
+        "$['x-tags'][1]: boolean does not match any of the union types",
+      ],
+      validate_json(r#"{"name": null, "age": "20", "role": "owner", "x-tags": ["a", true]}"#)
+    );
+    assert_eq!(vec!["$: expected object, found array"], validate_json("[]"));
+  }
+}
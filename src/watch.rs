@@ -0,0 +1,143 @@
+//! Regenerating on spec changes: polling modification times, and writing only what changed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Lists the files of the previous run, relative to the output directory.
+pub const MANIFEST: &str = ".draco-manifest";
+
+/// Writes `files` whose content differs from the disk and deletes the files an earlier run
+/// generated but this one didn't, along with directories left empty. Files missing from the
+/// manifest are never touched. Returns the written paths.
+pub fn write_output(output: &Path, files: &BTreeMap<String, String>) -> io::Result<Vec<String>> {
+  let mut written = vec![];
+  for (file, contents) in files {
+    if fs::read_to_string(file).ok().as_ref() == Some(contents) {
+      continue;
+    }
+    if let Some(dir) = Path::new(file).parent() {
+      fs::create_dir_all(dir)?;
+    }
+    fs::write(file, contents)?;
+    written.push(file.clone());
+  }
+  let generated = files
+    .keys()
+    .filter_map(|file| Path::new(file).strip_prefix(output).ok())
+    .map(Path::to_path_buf)
+    .collect::<BTreeSet<_>>();
+  let manifest = output.join(MANIFEST);
+  let previous = fs::read_to_string(&manifest).unwrap_or_default();
+  for line in previous.lines() {
+    let relative = PathBuf::from(line);
+    if line.is_empty() || generated.contains(&relative) || !is_plain_relative(&relative) {
+      continue;
+    }
+    let path = output.join(&relative);
+    if path.is_file() {
+      fs::remove_file(&path)?;
+    }
+    remove_empty_parents(output, &path)?;
+  }
+  let lines = generated
+    .iter()
+    .map(|path| format!("{}\n", path.to_string_lossy()))
+    .collect::<String>();
+  fs::write(manifest, lines)?;
+  Ok(written)
+}
+
+// a tampered manifest must not reach outside the output directory
+fn is_plain_relative(path: &Path) -> bool {
+  path
+    .components()
+    .all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn remove_empty_parents(output: &Path, path: &Path) -> io::Result<()> {
+  let mut dir = path.parent();
+  while let Some(current) = dir {
+    if current == output || !current.starts_with(output) {
+      break;
+    }
+    let is_empty = fs::read_dir(current)
+      .map(|mut entries| entries.next().is_none())
+      .unwrap_or(false);
+    if !is_empty {
+      break;
+    }
+    fs::remove_dir(current)?;
+    dir = current.parent();
+  }
+  Ok(())
+}
+
+// a missing file counts as a change too, editors often replace files by renaming
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+  paths
+    .iter()
+    .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+    .collect()
+}
+
+/// Blocks until one of `paths` changes, then until no change happened for `debounce`, so a
+/// save touching several files regenerates once.
+pub fn wait_for_change(paths: &[PathBuf], poll: Duration, debounce: Duration) {
+  let before = modified_times(paths);
+  let mut current = before.clone();
+  while current == before {
+    thread::sleep(poll);
+    current = modified_times(paths);
+  }
+  loop {
+    thread::sleep(debounce);
+    let next = modified_times(paths);
+    if next == current {
+      return;
+    }
+    current = next;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_write_only_changed_files() {
+    let dir = crate::TestDir::new("it-write-only-changed-files");
+    fs::write(dir.join("notes.txt"), "not generated").unwrap();
+    let file = |name: &str| dir.join(name).to_string_lossy().to_string();
+    let mut files = BTreeMap::new();
+    files.insert(
+      file("removed/Command.scala"),
+      "case class Command()".to_string(),
+    );
+    files.insert(file("routes"), "GET /users".to_string());
+    write_output(&dir, &files).unwrap();
+
+    files.remove(&file("removed/Command.scala"));
+    files.insert(file("a/Command.scala"), "case class Command()".to_string());
+    assert_eq!(
+      vec![file("a/Command.scala")],
+      write_output(&dir, &files).unwrap()
+    );
+    assert!(!dir.join("removed").exists());
+    assert!(dir.join("notes.txt").exists());
+    assert_eq!(
+      "a/Command.scala\nroutes\n",
+      fs::read_to_string(dir.join(MANIFEST)).unwrap()
+    );
+
+    files.insert(file("routes"), "GET /users\nPOST /users".to_string());
+    assert_eq!(vec![file("routes")], write_output(&dir, &files).unwrap());
+    assert_eq!(
+      "GET /users\nPOST /users",
+      fs::read_to_string(dir.join("routes")).unwrap()
+    );
+  }
+}